@@ -1,9 +1,20 @@
-use app_store_access::{client::SuggestionClient, country::Country, language::Language};
+use app_store_access::{
+    client::SuggestionClient,
+    country::Country,
+    crawl::{Budget, Frontier, Node},
+    creative::Change,
+    index::Store,
+    language::Language,
+    media::{Entry, MediaStore, Playlist, PlaylistError, Snapshot},
+    model::ImageType,
+    perceptual::{HashCache, Icon},
+};
 use app_store_access_apple::model::lookup::LookupResult;
-use app_store_access_google::request::params::search::PriceFilter;
+use app_store_access_google::request::params::{developer::DeveloperId, search::PriceFilter};
 use cli_helpers::prelude::*;
+use std::collections::HashMap;
 use std::io::{BufRead, BufReader};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 const LOOKUP_PAGE_SIZE: usize = 50;
@@ -22,10 +33,34 @@ pub enum Error {
     GoogleClient(#[from] app_store_access_google::client::Error),
     #[error("Google client suggestion error")]
     GoogleSuggestClient(#[from] app_store_access_google::client::suggest::Error),
-    #[error("CSV error")]
-    Csv(#[from] csv::Error),
+    #[error("Output error")]
+    Output(#[from] app_store_access::output::Error),
     #[error("JSON file error")]
     JsonFile(PathBuf, serde_json::Error),
+    #[error("Apple rank error")]
+    AppleRank(#[from] app_store_access_apple::rank::Error),
+    #[error("Google rank error")]
+    GoogleRank(#[from] app_store_access_google::rank::Error),
+    #[error("Crawl error")]
+    Crawl(#[from] app_store_access::crawl::Error),
+    #[error("Media store error")]
+    Media(#[from] app_store_access::media::Error),
+    #[error("Apple media error")]
+    AppleMedia(#[from] app_store_access_apple::media::Error),
+    #[error("Google media error")]
+    GoogleMedia(#[from] app_store_access_google::media::Error),
+    #[error("Perceptual hash error")]
+    Perceptual(#[from] app_store_access::perceptual::Error),
+}
+
+#[derive(Debug, thiserror::Error)]
+enum DownloadError {
+    #[error("HTTP error: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("Invalid URL: {0}")]
+    Url(#[from] url::ParseError),
+    #[error("{0}")]
+    Playlist(#[from] PlaylistError),
 }
 
 #[tokio::main]
@@ -46,9 +81,7 @@ async fn main() -> Result<(), Error> {
                 Default::default(),
             )?;
 
-            let mut writer = csv::WriterBuilder::new()
-                .has_headers(false)
-                .from_writer(std::io::stdout());
+            let mut writer = app_store_access::output::Writer::new(opts.format, std::io::stdout());
 
             match command {
                 ApiCommand::Search {
@@ -87,10 +120,7 @@ async fn main() -> Result<(), Error> {
                     full,
                     delay,
                 } => {
-                    let reader = BufReader::new(std::fs::File::open(query_file)?);
-                    let query_lines = reader.lines().collect::<Result<Vec<_>, _>>()?;
-
-                    for query in query_lines {
+                    for query in read_lines(query_file)? {
                         apple_search(
                             &apple_client,
                             &mut writer,
@@ -123,26 +153,546 @@ async fn main() -> Result<(), Error> {
                         .lookup_suggestions(&query, country, lang)
                         .await?
                     {
-                        writer.write_record([
-                            "apple".to_string(),
-                            query.to_string(),
-                            lang.to_string(),
-                            country.to_string(),
+                        writer.write(&SuggestionRecord {
+                            store: "apple",
+                            query: &query,
+                            lang,
+                            country,
                             suggestion,
-                        ])?;
+                        })?;
                     }
 
                     for suggestion in google_client
                         .lookup_suggestions(&query, country, lang)
                         .await?
                     {
-                        writer.write_record([
-                            "google".to_string(),
-                            query.to_string(),
-                            lang.to_string(),
-                            country.to_string(),
+                        writer.write(&SuggestionRecord {
+                            store: "google",
+                            query: &query,
+                            lang,
+                            country,
                             suggestion,
-                        ])?;
+                        })?;
+                    }
+                }
+            }
+
+            writer.flush()?;
+        }
+        Command::Keywords {
+            apple_archive,
+            google_archive,
+            command,
+        } => {
+            let apple_client = app_store_access_apple::client::Client::new(Some(&apple_archive));
+
+            let google_client = app_store_access_google::client::Client::new(
+                Some(&google_archive),
+                Default::default(),
+            )?;
+
+            let mut writer = app_store_access::output::Writer::new(opts.format, std::io::stdout());
+
+            match command {
+                KeywordsCommand::Expand {
+                    seed,
+                    depth,
+                    alphabet,
+                    country,
+                    lang,
+                } => {
+                    let apple_tree = app_store_access::keywords::expand(
+                        &apple_client,
+                        &seed,
+                        depth,
+                        &alphabet,
+                        country,
+                        lang,
+                    )
+                    .await?;
+
+                    for keyword in apple_tree.keywords() {
+                        writer.write(&KeywordRecord::new("apple", &seed, keyword))?;
+                    }
+
+                    let google_tree = app_store_access::keywords::expand(
+                        &google_client,
+                        &seed,
+                        depth,
+                        &alphabet,
+                        country,
+                        lang,
+                    )
+                    .await?;
+
+                    for keyword in google_tree.keywords() {
+                        writer.write(&KeywordRecord::new("google", &seed, keyword))?;
+                    }
+                }
+            }
+
+            writer.flush()?;
+        }
+        Command::Localization {
+            apple_archive,
+            google_archive,
+            apple_id,
+            google_id,
+            country,
+            langs,
+            baseline,
+            delay,
+        } => {
+            let apple_client = app_store_access_apple::client::Client::new(Some(&apple_archive));
+
+            let google_client = app_store_access_google::client::Client::new(
+                Some(&google_archive),
+                Default::default(),
+            )?;
+
+            let mut writer = app_store_access::output::Writer::new(opts.format, std::io::stdout());
+
+            let mut languages = vec![baseline];
+            languages.extend(langs.into_iter().filter(|language| *language != baseline));
+
+            // Lookups can be batched, so we make one request per language for each chunk of IDs.
+            let mut apple_listings = apple_id
+                .iter()
+                .map(|id| (*id, (vec![], None)))
+                .collect::<HashMap<u64, (Vec<_>, Option<Vec<Language>>)>>();
+
+            for language in &languages {
+                let mut found = HashMap::new();
+
+                for id_chunk in apple_id.chunks(LOOKUP_PAGE_SIZE) {
+                    let result = apple_client
+                        .lookup_ids(id_chunk, country, *language)
+                        .await?;
+
+                    for result in result.results {
+                        if let LookupResult::Software(software) = result {
+                            if let Some((_, declared)) = apple_listings.get_mut(&software.track_id)
+                            {
+                                declared
+                                    .get_or_insert_with(|| software.language_codes_iso_2_a.clone());
+                            }
+
+                            found.insert(
+                                software.track_id,
+                                app_store_access::localization::Listing {
+                                    title: software.track_name.to_string(),
+                                    description: software.description.to_string(),
+                                    screenshots: software
+                                        .screenshot_urls
+                                        .iter()
+                                        .map(std::string::ToString::to_string)
+                                        .collect(),
+                                },
+                            );
+                        }
+                    }
+
+                    tokio::time::sleep(Duration::from_millis(delay)).await;
+                }
+
+                for (id, (listings, _)) in &mut apple_listings {
+                    listings.push((*language, found.remove(id)));
+                }
+            }
+
+            for id in &apple_id {
+                if let Some((listings, declared)) = apple_listings.get(id) {
+                    for coverage in app_store_access::localization::coverage(
+                        app_store_access::index::Store::Apple,
+                        &id.to_string(),
+                        baseline,
+                        listings,
+                        declared.as_deref(),
+                    ) {
+                        writer.write(&coverage)?;
+                    }
+                }
+            }
+
+            writer.flush()?;
+
+            for id in &google_id {
+                let mut listings = vec![];
+
+                for language in &languages {
+                    let app = google_client.app(id, *language, country).await?;
+
+                    listings.push((
+                        *language,
+                        app.map(|app| app_store_access::localization::Listing {
+                            title: app.title,
+                            description: app.description,
+                            screenshots: app.images.screenshots,
+                        }),
+                    ));
+
+                    tokio::time::sleep(Duration::from_millis(delay)).await;
+                }
+
+                for coverage in app_store_access::localization::coverage(
+                    app_store_access::index::Store::Google,
+                    id,
+                    baseline,
+                    &listings,
+                    None,
+                ) {
+                    writer.write(&coverage)?;
+                }
+
+                writer.flush()?;
+            }
+        }
+        Command::Crawl {
+            store,
+            frontier: frontier_path,
+            apple_archive,
+            google_archive,
+            app,
+            developer,
+            query,
+            depth,
+            max_nodes,
+            country,
+            lang,
+            skip_developers,
+            skip_similar,
+            skip_suggestions,
+            delay,
+        } => {
+            let apple_client = app_store_access_apple::client::Client::new(Some(&apple_archive));
+
+            let google_client = app_store_access_google::client::Client::new(
+                Some(&google_archive),
+                Default::default(),
+            )?;
+
+            let mut writer = app_store_access::output::Writer::new(opts.format, std::io::stdout());
+
+            let mut frontier = Frontier::open(&frontier_path, store)?;
+            let budget = Budget {
+                max_depth: depth,
+                max_nodes,
+            };
+            let expansion = Expansion {
+                developers: !skip_developers,
+                similar: !skip_similar,
+                suggestions: !skip_suggestions,
+            };
+
+            let seeds = app
+                .into_iter()
+                .map(Node::App)
+                .chain(developer.into_iter().map(Node::Developer))
+                .chain(query.into_iter().map(Node::Query));
+
+            for node in seeds {
+                if frontier.seed(node.clone()) {
+                    writer.write(&CrawlRecord::new(store, &node, 0, None))?;
+                }
+            }
+
+            frontier.save(&frontier_path)?;
+
+            let mut expanded = 0;
+
+            while let Some(entry) = frontier.next().cloned() {
+                if !budget.allows(expanded) {
+                    log::info!("Crawl budget exhausted");
+                    break;
+                }
+
+                if expanded > 0 {
+                    tokio::time::sleep(Duration::from_millis(delay)).await;
+                }
+
+                let discovered = match store {
+                    Store::Apple => {
+                        apple_expand(&apple_client, &entry.node, country, lang, &expansion).await?
+                    }
+                    Store::Google => {
+                        google_expand(&google_client, &entry.node, country, lang, &expansion)
+                            .await?
+                    }
+                };
+
+                for node in frontier.complete(discovered, &budget) {
+                    writer.write(&CrawlRecord::new(
+                        store,
+                        &node,
+                        entry.depth + 1,
+                        Some(&entry.node),
+                    ))?;
+                }
+
+                frontier.save(&frontier_path)?;
+                writer.flush()?;
+                expanded += 1;
+            }
+
+            log::info!(
+                "Expanded {} nodes ({} in total), {} remaining",
+                expanded,
+                frontier.expanded(),
+                frontier.len()
+            );
+
+            writer.flush()?;
+        }
+        Command::Media {
+            apple_archive,
+            google_archive,
+            output,
+            apple_id,
+            google_id,
+            all,
+            width,
+            skip_videos,
+            delay,
+        } => {
+            let snapshots = archived_snapshots(
+                &apple_archive,
+                &google_archive,
+                &apple_id,
+                &google_id,
+                all,
+                width,
+            )?;
+
+            let client = reqwest::Client::new();
+            let mut media = MediaStore::open(&output)?;
+            let mut writer = app_store_access::output::Writer::new(opts.format, std::io::stdout());
+            let mut downloaded = 0;
+
+            for snapshot in snapshots {
+                if media.contains_snapshot(&snapshot) {
+                    continue;
+                }
+
+                let mut entries = vec![];
+                let mut complete = true;
+
+                for asset in snapshot
+                    .assets
+                    .iter()
+                    .filter(|asset| !(skip_videos && asset.image_type == ImageType::Video))
+                {
+                    let object = if let Some(object) = media.object_for_url(&asset.url) {
+                        object.clone()
+                    } else {
+                        if downloaded > 0 {
+                            tokio::time::sleep(Duration::from_millis(delay)).await;
+                        }
+
+                        downloaded += 1;
+
+                        match download_asset(&client, &asset.url).await {
+                            Ok((contents, extension)) => media.insert(&contents, &extension)?,
+                            Err(error) => {
+                                log::warn!("Failed to download {}: {}", asset.url, error);
+                                complete = false;
+                                continue;
+                            }
+                        }
+                    };
+
+                    entries.push(Entry::new(&snapshot, asset, object));
+                }
+
+                // Snapshots with failed downloads are not recorded, so that they are retried.
+                if complete {
+                    for entry in &entries {
+                        writer.write(&MediaRecord::new(entry))?;
+                    }
+
+                    media.record(entries)?;
+                    writer.flush()?;
+                }
+            }
+
+            log::info!("Downloaded {} files", downloaded);
+
+            writer.flush()?;
+        }
+        Command::ScreenshotChanges {
+            apple_archive,
+            google_archive,
+            apple_id,
+            google_id,
+            all,
+            media,
+            width,
+        } => {
+            let snapshots = archived_snapshots(
+                &apple_archive,
+                &google_archive,
+                &apple_id,
+                &google_id,
+                all,
+                width,
+            )?;
+
+            let media = media.map(MediaStore::open).transpose()?;
+            let mut writer = app_store_access::output::Writer::new(opts.format, std::io::stdout());
+
+            let changes = app_store_access::creative::changes(&snapshots, |url| {
+                media
+                    .as_ref()
+                    .and_then(|media| media.object_for_url(url))
+                    .map_or_else(|| url.to_string(), |object| object.hash.clone())
+            });
+
+            for change in &changes {
+                writer.write(&ScreenshotChangeRecord::new(change))?;
+            }
+
+            writer.flush()?;
+        }
+        Command::IconHashes { media } => {
+            let media = MediaStore::open(&media)?;
+            let mut writer = app_store_access::output::Writer::new(opts.format, std::io::stdout());
+
+            for icon in icon_hashes(&media)? {
+                writer.write(&IconHashRecord::new(&icon))?;
+            }
+
+            writer.flush()?;
+        }
+        Command::SimilarIcons {
+            media,
+            max_distance,
+            app,
+            cross_store,
+        } => {
+            let media = MediaStore::open(&media)?;
+            let mut writer = app_store_access::output::Writer::new(opts.format, std::io::stdout());
+
+            let icons = icon_hashes(&media)?;
+
+            for similar in app_store_access::perceptual::similar(&icons, max_distance, cross_store)
+            {
+                if app.is_empty()
+                    || app.contains(&similar.left.app_id)
+                    || app.contains(&similar.right.app_id)
+                {
+                    writer.write(&SimilarIconRecord {
+                        left_store: similar.left.store,
+                        left_app_id: &similar.left.app_id,
+                        right_store: similar.right.store,
+                        right_app_id: &similar.right.app_id,
+                        phash_distance: similar.distance.phash,
+                        dhash_distance: similar.distance.dhash,
+                        identical: similar.left.hash == similar.right.hash,
+                    })?;
+                }
+            }
+
+            writer.flush()?;
+        }
+        Command::Countries => {
+            let mut writer = app_store_access::output::Writer::new(opts.format, std::io::stdout());
+
+            for country in Country::known() {
+                writer.write(&CountryRecord {
+                    country,
+                    iso: country.is_iso(),
+                    apple_market: app_store_access_apple::request::markets::MarketCode::try_from(
+                        country,
+                    )
+                    .ok()
+                    .map(|market_code| market_code.0),
+                    google: app_store_access_google::request::markets::is_available(country),
+                })?;
+            }
+
+            writer.flush()?;
+        }
+        Command::Rank {
+            apple_archive,
+            google_archive,
+            command,
+        } => {
+            let mut writer = app_store_access::output::Writer::new(opts.format, std::io::stdout());
+
+            match command {
+                RankCommand::Track {
+                    keywords,
+                    apps,
+                    countries,
+                    lang,
+                    delay,
+                } => {
+                    let apple_client =
+                        app_store_access_apple::client::Client::new(Some(&apple_archive));
+
+                    let google_client = app_store_access_google::client::Client::new(
+                        Some(&google_archive),
+                        Default::default(),
+                    )?;
+
+                    let keywords = read_lines(keywords)?;
+                    let apps = read_lines(apps)?;
+
+                    // Every observation in this run shares the same timestamp.
+                    let timestamp = chrono::Utc::now();
+
+                    for country in countries {
+                        for keyword in &keywords {
+                            let page = apple_client.search(keyword, country, lang).await?;
+                            let run = app_store_access_apple::rank::run(
+                                timestamp, country, keyword, &page,
+                            );
+
+                            for observation in run.observations(&apps) {
+                                writer.write(&observation)?;
+                            }
+
+                            let results = google_client
+                                .search(keyword, lang, country, PriceFilter::default(), 100)
+                                .await?;
+                            let run = app_store_access_google::rank::run(
+                                timestamp, country, keyword, &results,
+                            );
+
+                            for observation in run.observations(&apps) {
+                                writer.write(&observation)?;
+                            }
+
+                            writer.flush()?;
+                            tokio::time::sleep(Duration::from_millis(delay)).await;
+                        }
+                    }
+                }
+                RankCommand::History { keywords, apps } => {
+                    let keywords = keywords
+                        .map(|keywords| {
+                            read_lines(keywords).map(|lines| {
+                                lines.into_iter().collect::<std::collections::HashSet<_>>()
+                            })
+                        })
+                        .transpose()?;
+                    let apps = read_lines(apps)?;
+
+                    let mut history = app_store_access::rank::History::default();
+
+                    for run in app_store_access_apple::rank::runs(&apple_archive)?
+                        .into_iter()
+                        .chain(app_store_access_google::rank::runs(&google_archive)?)
+                    {
+                        if keywords
+                            .as_ref()
+                            .is_none_or(|keywords| keywords.contains(&run.keyword))
+                        {
+                            history.add(&run, &apps);
+                        }
+                    }
+
+                    for observation in history.observations() {
+                        writer.write(&observation)?;
                     }
                 }
             }
@@ -159,6 +709,9 @@ async fn main() -> Result<(), Error> {
 struct Opts {
     #[clap(flatten)]
     verbose: Verbosity,
+    /// Output format: csv, csv-with-headers, or jsonl
+    #[clap(long, global = true, default_value = "csv")]
+    format: app_store_access::output::Format,
     #[clap(subcommand)]
     command: Command,
 }
@@ -174,6 +727,175 @@ enum Command {
         #[clap(subcommand)]
         command: ApiCommand,
     },
+    /// Discover keywords by expanding autocomplete suggestions
+    Keywords {
+        #[clap(long, default_value = "data/apple/")]
+        apple_archive: PathBuf,
+        #[clap(long, default_value = "data/google/")]
+        google_archive: PathBuf,
+        #[clap(subcommand)]
+        command: KeywordsCommand,
+    },
+    /// Compare app listings across languages and print a localization matrix
+    Localization {
+        #[clap(long, default_value = "data/apple/")]
+        apple_archive: PathBuf,
+        #[clap(long, default_value = "data/google/")]
+        google_archive: PathBuf,
+        /// Apple app ID (option can be provided multiple times)
+        #[clap(long)]
+        apple_id: Vec<u64>,
+        /// Google app ID (option can be provided multiple times)
+        #[clap(long)]
+        google_id: Vec<String>,
+        #[clap(long, default_value = "us")]
+        country: Country,
+        #[clap(long, required = true, value_delimiter = ',')]
+        langs: Vec<Language>,
+        /// Language of the default listing that the other languages are compared against
+        #[clap(long, default_value = "en")]
+        baseline: Language,
+        /// Time to wait between requests in milliseconds
+        #[clap(long, default_value = "500")]
+        delay: u64,
+    },
+    /// Discover apps breadth-first from seed apps, developers, and search queries
+    ///
+    /// The frontier is saved after each step, so an interrupted crawl can be resumed by running
+    /// the same command again (seeds that have already been visited are ignored).
+    Crawl {
+        #[clap(long)]
+        store: Store,
+        /// JSON file for the crawl state (created if it does not exist)
+        #[clap(long)]
+        frontier: PathBuf,
+        #[clap(long, default_value = "data/apple/")]
+        apple_archive: PathBuf,
+        #[clap(long, default_value = "data/google/")]
+        google_archive: PathBuf,
+        /// Seed app ID (option can be provided multiple times)
+        #[clap(long)]
+        app: Vec<String>,
+        /// Seed developer ID (option can be provided multiple times)
+        #[clap(long)]
+        developer: Vec<String>,
+        /// Seed search query (option can be provided multiple times)
+        #[clap(long)]
+        query: Vec<String>,
+        /// Maximum number of links to follow from a seed
+        #[clap(long, default_value = "2")]
+        depth: usize,
+        /// Maximum number of nodes to expand in this run
+        #[clap(long)]
+        max_nodes: Option<usize>,
+        #[clap(long, default_value = "us")]
+        country: Country,
+        #[clap(long, default_value = "en")]
+        lang: Language,
+        /// Do not follow links between apps and developers
+        #[clap(long)]
+        skip_developers: bool,
+        /// Do not follow related apps (Apple only)
+        #[clap(long)]
+        skip_similar: bool,
+        /// Do not expand queries into autocomplete suggestions
+        #[clap(long)]
+        skip_suggestions: bool,
+        /// Time to wait between requests in milliseconds
+        #[clap(long, default_value = "500")]
+        delay: u64,
+    },
+    /// Download icons, screenshots, and preview videos listed in archived app pages
+    ///
+    /// Files are stored by content hash, and a manifest records the assets listed for each app in
+    /// each archived snapshot. Snapshots that are already in the manifest are skipped.
+    Media {
+        #[clap(long, default_value = "data/apple/")]
+        apple_archive: PathBuf,
+        #[clap(long, default_value = "data/google/")]
+        google_archive: PathBuf,
+        /// Directory for downloaded files and the manifest
+        #[clap(long, default_value = "data/media/")]
+        output: PathBuf,
+        /// Apple app ID (option can be provided multiple times)
+        #[clap(long)]
+        apple_id: Vec<u64>,
+        /// Google app ID (option can be provided multiple times)
+        #[clap(long)]
+        google_id: Vec<String>,
+        /// Download assets for every archived app in both stores
+        #[clap(long, conflicts_with_all = ["apple_id", "google_id"])]
+        all: bool,
+        /// Maximum image width in pixels
+        #[clap(long, default_value = "1024")]
+        width: usize,
+        /// Do not download preview videos (Apple videos are downloaded segment by segment)
+        #[clap(long)]
+        skip_videos: bool,
+        /// Time to wait between image requests in milliseconds
+        #[clap(long, default_value = "500")]
+        delay: u64,
+    },
+    /// Print changes to the screenshots listed in archived app pages
+    ///
+    /// Consecutive snapshots of each listing (app, country, and language) are compared for each
+    /// device type, and each change is printed with the screenshot URLs before and after (separated
+    /// by spaces).
+    ScreenshotChanges {
+        #[clap(long, default_value = "data/apple/")]
+        apple_archive: PathBuf,
+        #[clap(long, default_value = "data/google/")]
+        google_archive: PathBuf,
+        /// Apple app ID (option can be provided multiple times)
+        #[clap(long)]
+        apple_id: Vec<u64>,
+        /// Google app ID (option can be provided multiple times)
+        #[clap(long)]
+        google_id: Vec<String>,
+        /// Compare every archived app in both stores
+        #[clap(long, conflicts_with_all = ["apple_id", "google_id"])]
+        all: bool,
+        /// Compare screenshots downloaded by the media command by content hash instead of URL
+        #[clap(long)]
+        media: Option<PathBuf>,
+        /// Image width (this should match the width used by the media command)
+        #[clap(long, default_value = "1024")]
+        width: usize,
+    },
+    /// Print perceptual hashes (dHash and pHash) of the latest downloaded icon for each app
+    ///
+    /// Hashes are cached in the media directory, so each file is only decoded once.
+    IconHashes {
+        /// Directory used by the media command
+        #[clap(long, default_value = "data/media/")]
+        media: PathBuf,
+    },
+    /// Find apps with near-duplicate icons, within and across stores
+    SimilarIcons {
+        /// Directory used by the media command
+        #[clap(long, default_value = "data/media/")]
+        media: PathBuf,
+        /// Maximum number of pHash bits that may differ (out of 64)
+        #[clap(long, default_value = "8")]
+        max_distance: u32,
+        /// Only print pairs that include this app ID (option can be provided multiple times)
+        #[clap(long)]
+        app: Vec<String>,
+        /// Only print pairs of apps from different stores
+        #[clap(long)]
+        cross_store: bool,
+    },
+    /// Print every known country with the stores that operate there
+    Countries,
+    /// Track keyword search rank for a set of apps
+    Rank {
+        #[clap(long, default_value = "data/apple/")]
+        apple_archive: PathBuf,
+        #[clap(long, default_value = "data/google/")]
+        google_archive: PathBuf,
+        #[clap(subcommand)]
+        command: RankCommand,
+    },
 }
 
 #[derive(Debug, Parser)]
@@ -219,9 +941,516 @@ enum ApiCommand {
     },
 }
 
+#[derive(Debug, Parser)]
+enum KeywordsCommand {
+    /// Recursively query the seed followed by each character of the alphabet
+    Expand {
+        #[clap(long)]
+        seed: String,
+        #[clap(long, default_value = "1")]
+        depth: usize,
+        #[clap(long, default_value = app_store_access::keywords::DEFAULT_ALPHABET)]
+        alphabet: String,
+        #[clap(long, default_value = "us")]
+        country: Country,
+        #[clap(long, default_value = "en")]
+        lang: Language,
+    },
+}
+
+#[derive(Debug, Parser)]
+enum RankCommand {
+    /// Search for each keyword in each country and print the rank of each tracked app
+    Track {
+        /// Text file with one keyword per line
+        #[clap(long)]
+        keywords: PathBuf,
+        /// Text file with one app ID per line (numeric for Apple, package name for Google)
+        #[clap(long)]
+        apps: PathBuf,
+        #[clap(long, default_value = "us", value_delimiter = ',')]
+        countries: Vec<Country>,
+        #[clap(long, default_value = "en")]
+        lang: Language,
+        /// Time to wait between keywords in milliseconds
+        #[clap(long, default_value = "500")]
+        delay: u64,
+    },
+    /// Rebuild rank history for the tracked apps from archived searches
+    History {
+        /// Text file with one keyword per line (all archived searches are used if not provided)
+        #[clap(long)]
+        keywords: Option<PathBuf>,
+        /// Text file with one app ID per line (numeric for Apple, package name for Google)
+        #[clap(long)]
+        apps: PathBuf,
+    },
+}
+
+/// A search result from either store.
+///
+/// Google apps do not have numeric IDs, so for Google results `id` is always empty and the app ID
+/// is provided as `bundle_id`.
+#[derive(serde::Serialize)]
+struct SearchResultRecord<'a> {
+    store: &'static str,
+    query: &'a str,
+    id: Option<u64>,
+    bundle_id: Option<&'a str>,
+    developer_id: Option<String>,
+    title: &'a str,
+    developer_name: &'a str,
+}
+
+#[derive(serde::Serialize)]
+struct SuggestionRecord<'a> {
+    store: &'static str,
+    query: &'a str,
+    #[serde(with = "app_store_access::language::language_code")]
+    lang: Language,
+    #[serde(with = "app_store_access::country::country_code")]
+    country: Country,
+    suggestion: String,
+}
+
+/// A country, with its Apple market code (if the App Store operates there) and whether Google Play
+/// operates there.
+#[derive(serde::Serialize)]
+struct CountryRecord {
+    #[serde(with = "app_store_access::country::country_code")]
+    country: Country,
+    iso: bool,
+    apple_market: Option<u32>,
+    google: bool,
+}
+
+#[derive(serde::Serialize)]
+struct KeywordRecord<'a> {
+    store: &'static str,
+    seed: &'a str,
+    term: &'a str,
+    depth: usize,
+    parent: Option<&'a str>,
+}
+
+impl<'a> KeywordRecord<'a> {
+    fn new(
+        store: &'static str,
+        seed: &'a str,
+        keyword: &'a app_store_access::keywords::Keyword,
+    ) -> Self {
+        Self {
+            store,
+            seed,
+            term: &keyword.term,
+            depth: keyword.depth,
+            parent: keyword.parent.as_deref(),
+        }
+    }
+}
+
+#[derive(serde::Serialize)]
+struct CrawlRecord<'a> {
+    store: Store,
+    kind: &'static str,
+    id: &'a str,
+    depth: usize,
+    parent_kind: Option<&'static str>,
+    parent_id: Option<&'a str>,
+}
+
+impl<'a> CrawlRecord<'a> {
+    fn new(store: Store, node: &'a Node, depth: usize, parent: Option<&'a Node>) -> Self {
+        Self {
+            store,
+            kind: node.kind(),
+            id: node.id(),
+            depth,
+            parent_kind: parent.map(Node::kind),
+            parent_id: parent.map(Node::id),
+        }
+    }
+}
+
+#[derive(serde::Serialize)]
+struct MediaRecord<'a> {
+    store: Store,
+    app_id: &'a str,
+    timestamp: chrono::DateTime<chrono::Utc>,
+    image_type: ImageType,
+    device: Option<&'a str>,
+    position: usize,
+    hash: &'a str,
+    extension: &'a str,
+    size: usize,
+    url: &'a str,
+}
+
+impl<'a> MediaRecord<'a> {
+    fn new(entry: &'a Entry) -> Self {
+        Self {
+            store: entry.store,
+            app_id: &entry.app_id,
+            timestamp: entry.timestamp,
+            image_type: entry.image_type,
+            device: entry.device.as_deref(),
+            position: entry.position,
+            hash: &entry.object.hash,
+            extension: &entry.object.extension,
+            size: entry.object.size,
+            url: &entry.url,
+        }
+    }
+}
+
+#[derive(serde::Serialize)]
+struct ScreenshotChangeRecord<'a> {
+    store: Store,
+    app_id: &'a str,
+    #[serde(with = "app_store_access::country::country_code")]
+    country: Country,
+    language: Option<&'a str>,
+    device: Option<&'a str>,
+    before_timestamp: chrono::DateTime<chrono::Utc>,
+    after_timestamp: chrono::DateTime<chrono::Utc>,
+    added: usize,
+    removed: usize,
+    reordered: bool,
+    before: String,
+    after: String,
+}
+
+impl<'a> ScreenshotChangeRecord<'a> {
+    fn new(change: &'a Change) -> Self {
+        Self {
+            store: change.store,
+            app_id: &change.app_id,
+            country: change.country,
+            language: change.language.as_ref().map(Language::as_str),
+            device: change.device.as_deref(),
+            before_timestamp: change.before_timestamp,
+            after_timestamp: change.after_timestamp,
+            added: change.added,
+            removed: change.removed,
+            reordered: change.reordered(),
+            before: change.before.join(" "),
+            after: change.after.join(" "),
+        }
+    }
+}
+
+#[derive(serde::Serialize)]
+struct IconHashRecord<'a> {
+    store: Store,
+    app_id: &'a str,
+    timestamp: chrono::DateTime<chrono::Utc>,
+    hash: &'a str,
+    dhash: String,
+    phash: String,
+}
+
+impl<'a> IconHashRecord<'a> {
+    fn new(icon: &'a Icon) -> Self {
+        Self {
+            store: icon.store,
+            app_id: &icon.app_id,
+            timestamp: icon.timestamp,
+            hash: &icon.hash,
+            dhash: format!("{:016x}", icon.hashes.dhash),
+            phash: format!("{:016x}", icon.hashes.phash),
+        }
+    }
+}
+
+#[derive(serde::Serialize)]
+struct SimilarIconRecord<'a> {
+    left_store: Store,
+    left_app_id: &'a str,
+    right_store: Store,
+    right_app_id: &'a str,
+    phash_distance: u32,
+    dhash_distance: u32,
+    /// Whether the icon files are byte-for-byte identical.
+    identical: bool,
+}
+
+/// Which kinds of links a crawl follows.
+struct Expansion {
+    developers: bool,
+    similar: bool,
+    suggestions: bool,
+}
+
+async fn apple_expand(
+    client: &app_store_access_apple::client::Client,
+    node: &Node,
+    country: Country,
+    lang: Language,
+    expansion: &Expansion,
+) -> Result<Vec<Node>, Error> {
+    let mut discovered = vec![];
+
+    match node {
+        Node::App(id) => match id.parse::<u64>() {
+            Ok(id) => match client.app(id, country, Some(lang)).await? {
+                Some(page) => {
+                    if expansion.developers
+                        && let Some(app) = page.store_platform_data.product_dv.results.get(&id)
+                    {
+                        discovered.push(Node::Developer(app.artist_id.to_string()));
+                    }
+
+                    if expansion.similar {
+                        discovered.extend(
+                            page.related_app_ids()
+                                .into_iter()
+                                .map(|id| Node::App(id.to_string())),
+                        );
+                    }
+                }
+                None => {
+                    log::warn!("App not found: {}", id);
+                }
+            },
+            Err(_) => {
+                log::warn!("Invalid Apple app ID: {}", id);
+            }
+        },
+        Node::Developer(id) => match id.parse::<u64>() {
+            Ok(id) => match client.developer(id, country, lang).await? {
+                Some(developer) => {
+                    discovered.extend(
+                        developer
+                            .apps
+                            .iter()
+                            .map(|software| Node::App(software.track_id.to_string())),
+                    );
+                }
+                None => {
+                    log::warn!("Developer not found: {}", id);
+                }
+            },
+            Err(_) => {
+                log::warn!("Invalid Apple developer ID: {}", id);
+            }
+        },
+        Node::Query(query) => {
+            let page = client.search(query, country, lang).await?;
+
+            discovered.extend(
+                page.bubbles
+                    .bubbles()
+                    .into_iter()
+                    .flat_map(|bubbles| bubbles.results)
+                    .map(|result| Node::App(result.id.to_string())),
+            );
+
+            if expansion.suggestions {
+                discovered.extend(
+                    client
+                        .lookup_suggestions(query, country, lang)
+                        .await?
+                        .into_iter()
+                        .map(Node::Query),
+                );
+            }
+        }
+    }
+
+    Ok(discovered)
+}
+
+async fn google_expand(
+    client: &app_store_access_google::client::Client,
+    node: &Node,
+    country: Country,
+    lang: Language,
+    expansion: &Expansion,
+) -> Result<Vec<Node>, Error> {
+    let mut discovered = vec![];
+
+    match node {
+        Node::App(id) => match client.app(id, lang, country).await? {
+            Some(app) => {
+                if expansion.developers {
+                    discovered.push(Node::Developer(app.developer.id.to_string()));
+                }
+            }
+            None => {
+                log::warn!("App not found: {}", id);
+            }
+        },
+        Node::Developer(id) => {
+            let developer_id = id
+                .parse::<u64>()
+                .map_or_else(|_| DeveloperId::Name(id.clone()), DeveloperId::Numeric);
+
+            match client
+                .developer(&developer_id, lang, country, 100, false)
+                .await?
+            {
+                Some(portfolio) => {
+                    discovered.extend(portfolio.apps.into_iter().map(|app| Node::App(app.app_id)));
+                }
+                None => {
+                    log::warn!("Developer not found: {}", id);
+                }
+            }
+        }
+        Node::Query(query) => {
+            let results = client
+                .search(query, lang, country, PriceFilter::All, 100)
+                .await?;
+
+            discovered.extend(results.into_iter().map(|app| Node::App(app.id)));
+
+            if expansion.suggestions {
+                discovered.extend(
+                    client
+                        .lookup_suggestions(query, country, lang)
+                        .await?
+                        .into_iter()
+                        .map(Node::Query),
+                );
+            }
+        }
+    }
+
+    Ok(discovered)
+}
+
+/// Media snapshots from archived app pages for the given apps (or for every app if `all` is set).
+fn archived_snapshots(
+    apple_archive: &Path,
+    google_archive: &Path,
+    apple_id: &[u64],
+    google_id: &[String],
+    all: bool,
+    width: usize,
+) -> Result<Vec<Snapshot>, Error> {
+    let mut snapshots = vec![];
+
+    if all || !apple_id.is_empty() {
+        snapshots.extend(app_store_access_apple::media::snapshots(
+            apple_archive,
+            apple_id,
+            width,
+        )?);
+    }
+
+    if all || !google_id.is_empty() {
+        snapshots.extend(app_store_access_google::media::snapshots(
+            google_archive,
+            google_id,
+            width,
+        )?);
+    }
+
+    Ok(snapshots)
+}
+
+/// Perceptual hashes for the latest icon of each app in the media store.
+///
+/// Icons that cannot be decoded are skipped with a warning.
+fn icon_hashes(media: &MediaStore) -> Result<Vec<Icon>, Error> {
+    let mut cache = HashCache::open(media)?;
+    let mut icons = vec![];
+
+    for entry in app_store_access::perceptual::latest_icons(media.entries()) {
+        match cache.compute(&entry.object.hash, media.path(&entry.object)) {
+            Ok(hashes) => icons.push(Icon::new(entry, hashes)),
+            Err(app_store_access::perceptual::Error::Image(error)) => {
+                log::warn!(
+                    "Invalid icon for {} ({}): {}",
+                    entry.app_id,
+                    entry.store,
+                    error
+                );
+            }
+            Err(error) => return Err(error.into()),
+        }
+    }
+
+    Ok(icons)
+}
+
+/// Download a file, returning its contents and content type.
+async fn download(
+    client: &reqwest::Client,
+    url: &str,
+) -> Result<(Vec<u8>, Option<String>), reqwest::Error> {
+    let response = client.get(url).send().await?.error_for_status()?;
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(ToString::to_string);
+    let contents = response.bytes().await?;
+
+    Ok((contents.to_vec(), content_type))
+}
+
+/// Download an asset, returning its contents and a file extension.
+///
+/// HLS playlists (used for Apple preview videos) are resolved to the segments of their
+/// highest-bandwidth variant, which are concatenated into a single file.
+async fn download_asset(
+    client: &reqwest::Client,
+    url: &str,
+) -> Result<(Vec<u8>, String), DownloadError> {
+    let (contents, content_type) = download(client, url).await?;
+
+    if !Playlist::is_playlist(&contents) {
+        return Ok((
+            contents,
+            app_store_access::media::extension(url, content_type.as_deref()),
+        ));
+    }
+
+    let mut base = url::Url::parse(url)?;
+    let mut playlist = String::from_utf8(contents).map_err(|_| PlaylistError::Invalid)?;
+
+    let variant = Playlist::parse(&playlist)?
+        .best_variant()
+        .map(|variant| base.join(variant.uri))
+        .transpose()?;
+
+    if let Some(variant) = variant {
+        let (contents, _) = download(client, variant.as_str()).await?;
+        playlist = String::from_utf8(contents).map_err(|_| PlaylistError::Invalid)?;
+        base = variant;
+    }
+
+    let media_playlist = Playlist::parse(&playlist)?;
+
+    match (&media_playlist, media_playlist.extension()) {
+        (Playlist::Media { init, segments }, Some(extension)) => {
+            let mut video = vec![];
+
+            for uri in init.iter().chain(segments) {
+                let (contents, _) = download(client, base.join(uri)?.as_str()).await?;
+                video.extend(contents);
+            }
+
+            Ok((video, extension.to_string()))
+        }
+        // A variant that is itself a multivariant playlist.
+        _ => Err(PlaylistError::Invalid.into()),
+    }
+}
+
+fn read_lines(path: PathBuf) -> Result<Vec<String>, std::io::Error> {
+    let reader = BufReader::new(std::fs::File::open(path)?);
+
+    reader
+        .lines()
+        .filter(|line| line.as_ref().is_ok_and(|line| !line.trim().is_empty()))
+        .collect()
+}
+
 async fn apple_search<W: std::io::Write>(
     client: &app_store_access_apple::client::Client,
-    writer: &mut csv::Writer<W>,
+    writer: &mut app_store_access::output::Writer<W>,
     country: Country,
     lang: Language,
     full: bool,
@@ -246,7 +1475,7 @@ async fn apple_search<W: std::io::Write>(
         log::info!("Downloading full information for {} Apple apps", ids.len());
 
         for id in ids {
-            match client.app(id, country).await? {
+            match client.app(id, country, Some(lang)).await? {
                 Some(page) => {
                     let app = page
                         .store_platform_data
@@ -255,19 +1484,15 @@ async fn apple_search<W: std::io::Write>(
                         .get(&id)
                         .unwrap();
 
-                    writer.write_record([
-                        "apple".to_string(),
-                        query.to_string(),
-                        id.to_string(),
-                        app.common
-                            .bundle_id
-                            .as_ref()
-                            .map(|id| id.to_string())
-                            .unwrap_or_default(),
-                        app.artist_id.to_string(),
-                        app.common.name.to_string(),
-                        app.common.artist_name.to_string(),
-                    ])?;
+                    writer.write(&SearchResultRecord {
+                        store: "apple",
+                        query,
+                        id: Some(id),
+                        bundle_id: app.common.bundle_id.as_deref(),
+                        developer_id: Some(app.artist_id.to_string()),
+                        title: &app.common.name,
+                        developer_name: &app.common.artist_name,
+                    })?;
 
                     writer.flush()?;
                 }
@@ -287,15 +1512,15 @@ async fn apple_search<W: std::io::Write>(
             for result in result.results {
                 match &result {
                     LookupResult::Software(software) => {
-                        writer.write_record([
-                            "apple".to_string(),
-                            query.to_string(),
-                            software.track_id.to_string(),
-                            software.bundle_id.to_string(),
-                            software.artist_id.to_string(),
-                            software.track_name.to_string(),
-                            software.artist_name.to_string(),
-                        ])?;
+                        writer.write(&SearchResultRecord {
+                            store: "apple",
+                            query,
+                            id: Some(software.track_id),
+                            bundle_id: Some(&software.bundle_id),
+                            developer_id: Some(software.artist_id.to_string()),
+                            title: &software.track_name,
+                            developer_name: &software.artist_name,
+                        })?;
                     }
                     LookupResult::Artist(artist) => {
                         log::info!("Unexpected artist result: {}", artist.artist_id,);
@@ -310,7 +1535,7 @@ async fn apple_search<W: std::io::Write>(
 
 async fn google_search<W: std::io::Write>(
     client: &app_store_access_google::client::Client,
-    writer: &mut csv::Writer<W>,
+    writer: &mut app_store_access::output::Writer<W>,
     country: Country,
     lang: Language,
     full: bool,
@@ -329,17 +1554,17 @@ async fn google_search<W: std::io::Write>(
     }
 
     for app in results {
-        writer.write_record([
-            "google".to_string(),
-            query.to_string(),
-            "".to_string(),
-            app.id.clone(),
-            app.developer_id()
-                .map(|developer_id| developer_id.to_string())
-                .unwrap_or_default(),
-            app.title,
-            app.developer.name,
-        ])?;
+        writer.write(&SearchResultRecord {
+            store: "google",
+            query,
+            id: None,
+            bundle_id: Some(&app.id),
+            developer_id: app
+                .developer_id()
+                .map(|developer_id| developer_id.to_string()),
+            title: &app.title,
+            developer_name: &app.developer.name,
+        })?;
 
         if full {
             client.app(&app.id, lang, country).await?;
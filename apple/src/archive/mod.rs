@@ -11,6 +11,8 @@ pub enum Data<'a> {
     Search(super::model::search::Page<'a>),
     Lookup(super::model::lookup::LookupResultList<'a>),
     Reviews(super::model::reviews::Page<'a>),
+    Chart(super::model::chart::Chart),
+    Suggestions(super::model::suggest::HintList<'a>),
 }
 
 impl bounded_static::IntoBoundedStatic for Data<'_> {
@@ -22,6 +24,8 @@ impl bounded_static::IntoBoundedStatic for Data<'_> {
             Self::Search(page) => Self::Static::Search(page.into_static()),
             Self::Lookup(list) => Self::Static::Lookup(list.into_static()),
             Self::Reviews(page) => Self::Static::Reviews(page.into_static()),
+            Self::Chart(chart) => Self::Static::Chart(chart),
+            Self::Suggestions(list) => Self::Static::Suggestions(list.into_static()),
         }
     }
 }
@@ -59,7 +63,7 @@ impl Archiveable for Data<'static> {
                     (field, response.map(|data| Self::Lookup(data.into_static())))
                 }))
             }
-            Request::LookupBundleIds { .. } => {
+            Request::LookupBundleIds { .. } | Request::Developer { .. } => {
                 let next = map
                     .next_entry::<Field, Response<'_, super::model::lookup::LookupResultList<'de>>>(
                     )?;
@@ -79,6 +83,22 @@ impl Archiveable for Data<'static> {
                     )
                 }))
             }
+            Request::GenreTop { .. } => {
+                let next = map.next_entry::<Field, Response<'_, super::model::chart::Chart>>()?;
+
+                Ok(next.map(|(field, response)| (field, response.map(Self::Chart))))
+            }
+            Request::Suggest { .. } => {
+                let next =
+                    map.next_entry::<Field, Response<'_, super::model::suggest::HintList<'de>>>()?;
+
+                Ok(next.map(|(field, response)| {
+                    (
+                        field,
+                        response.map(|data| Self::Suggestions(data.into_static())),
+                    )
+                }))
+            }
             Request::Ratings { .. } => {
                 Err(serde::de::Error::custom("unsupported rating archive entry"))
             }
@@ -1,6 +1,19 @@
 use app_store_access::{client::SuggestionClient, country::Country, language::Language};
-use app_store_access_apple::{archive::Data, model::lookup::LookupResult, request::Request};
+use app_store_access_apple::{
+    archive::Data,
+    compatibility::{Compatibility, Family},
+    model::{
+        chart,
+        devices::Device,
+        full::{ProductDvResult, Relation},
+        genre::Genre,
+        lookup::{Artist, LookupResult, Software},
+    },
+    request::{Request, params::chart::ChartType},
+};
 use cli_helpers::prelude::*;
+use num_traits::ToPrimitive;
+use std::collections::{HashSet, VecDeque};
 use std::io::Write;
 use std::path::PathBuf;
 use std::time::Duration;
@@ -17,10 +30,20 @@ pub enum Error {
     Client(#[from] app_store_access_apple::client::Error),
     #[error("Client suggestion error")]
     SuggestClient(#[from] app_store_access_apple::client::suggest::Error),
-    #[error("CSV error")]
-    Csv(#[from] csv::Error),
+    #[error("Output error")]
+    Output(#[from] app_store_access::output::Error),
     #[error("JSON file error")]
     JsonFile(PathBuf, serde_json::Error),
+    #[error("Index error")]
+    Index(#[from] app_store_access::index::Error),
+    #[error("Index build error")]
+    IndexBuild(#[from] app_store_access_apple::index::Error),
+    #[error("Export error")]
+    Export(#[from] app_store_access_apple::export::Error),
+    #[error("Money error")]
+    Money(#[from] app_store_access::money::Error),
+    #[error("Unknown genre ID")]
+    UnknownGenre(u16),
 }
 
 impl Error {
@@ -44,12 +67,16 @@ async fn main() -> Result<(), Error> {
         Command::Api { archive, command } => {
             let client = app_store_access_apple::client::Client::new(Some(archive));
 
-            let mut writer = csv::WriterBuilder::new()
-                .has_headers(false)
-                .from_writer(std::io::stdout());
+            let mut writer = app_store_access::output::Writer::new(opts.format, std::io::stdout());
 
             match command {
-                ApiCommand::App { id, country } => match client.app(id, country).await? {
+                ApiCommand::App {
+                    id,
+                    country,
+                    lang,
+                    all_countries: false,
+                    ..
+                } => match client.app(id, country, lang).await? {
                     Some(page) => {
                         let app = page
                             .store_platform_data
@@ -58,20 +85,65 @@ async fn main() -> Result<(), Error> {
                             .get(&id)
                             .unwrap();
 
-                        writer.write_record([
-                            id.to_string(),
-                            app.common
-                                .bundle_id
-                                .as_ref()
-                                .map(|id| id.to_string())
-                                .unwrap_or_default(),
-                            app.common.name.to_string(),
-                        ])?;
+                        writer.write(&AppRecord::new(id, app))?;
                     }
                     None => {
-                        log::warn!("App not found: {}", id);
+                        ::log::warn!("App not found: {}", id);
                     }
                 },
+                ApiCommand::App {
+                    id,
+                    lang,
+                    all_countries: true,
+                    rates,
+                    delay,
+                    ..
+                } => {
+                    let rates = rates
+                        .map(app_store_access::money::Rates::load)
+                        .transpose()?;
+
+                    for country in
+                        app_store_access_apple::request::markets::MarketCode::known_countries()
+                    {
+                        let page = client.app(id, country, lang).await?;
+                        let app = page
+                            .as_ref()
+                            .and_then(|page| page.store_platform_data.product_dv.results.get(&id));
+
+                        if page.is_some() && app.is_none() {
+                            ::log::warn!("Empty app result for {}: {}", country, id);
+                        }
+
+                        // App pages do not include the storefront's currency, so we look it up.
+                        let currency = if app.is_some() {
+                            client
+                                .lookup_ids(&[id], country, lang.unwrap_or(Language::English))
+                                .await?
+                                .results
+                                .iter()
+                                .find_map(|result| match result {
+                                    LookupResult::Software(software) => {
+                                        software.currency.try_into().ok()
+                                    }
+                                    LookupResult::Artist(_) => None,
+                                })
+                        } else {
+                            None
+                        };
+
+                        writer.write(&AvailabilityRecord::new(
+                            id,
+                            country,
+                            app,
+                            currency,
+                            rates.as_ref(),
+                        ))?;
+                        writer.flush()?;
+
+                        tokio::time::sleep(Duration::from_millis(delay)).await;
+                    }
+                }
                 ApiCommand::Search {
                     query,
                     country,
@@ -94,10 +166,10 @@ async fn main() -> Result<(), Error> {
                         .unwrap_or_default();
 
                     if full {
-                        log::info!("Downloading full information for {} apps", ids.len());
+                        ::log::info!("Downloading full information for {} apps", ids.len());
 
                         for id in ids {
-                            match client.app(id, country).await? {
+                            match client.app(id, country, Some(lang)).await? {
                                 Some(page) => {
                                     let app = page
                                         .store_platform_data
@@ -106,27 +178,19 @@ async fn main() -> Result<(), Error> {
                                         .get(&id)
                                         .unwrap();
 
-                                    writer.write_record([
-                                        id.to_string(),
-                                        app.common
-                                            .bundle_id
-                                            .as_ref()
-                                            .map(|id| id.to_string())
-                                            .unwrap_or_default(),
-                                        app.common.name.to_string(),
-                                    ])?;
+                                    writer.write(&AppRecord::new(id, app))?;
 
                                     writer.flush()?;
                                 }
                                 None => {
-                                    log::warn!("App not found: {}", id);
+                                    ::log::warn!("App not found: {}", id);
                                 }
                             }
 
                             tokio::time::sleep(Duration::from_millis(delay)).await;
                         }
                     } else {
-                        log::info!("Downloading {} search results", ids.len());
+                        ::log::info!("Downloading {} search results", ids.len());
 
                         for id_chunk in ids.chunks(LOOKUP_PAGE_SIZE) {
                             let result = client.lookup_ids(id_chunk, country, lang).await?;
@@ -134,16 +198,10 @@ async fn main() -> Result<(), Error> {
                             for result in result.results {
                                 match &result {
                                     LookupResult::Software(software) => {
-                                        writer.write_record([
-                                            software.track_id.to_string(),
-                                            software.bundle_id.to_string(),
-                                            software.artist_id.to_string(),
-                                            software.track_name.to_string(),
-                                            software.artist_name.to_string(),
-                                        ])?;
+                                        writer.write(&SoftwareRecord::new(software))?;
                                     }
                                     LookupResult::Artist(artist) => {
-                                        log::info!(
+                                        ::log::info!(
                                             "Unexpected artist result: {}",
                                             artist.artist_id,
                                         );
@@ -153,36 +211,72 @@ async fn main() -> Result<(), Error> {
                         }
                     }
                 }
-                ApiCommand::LookupIds { id, country, lang } => {
+                ApiCommand::LookupIds {
+                    id,
+                    country,
+                    lang,
+                    all_countries: false,
+                    ..
+                } => {
                     for id_chunk in id.chunks(LOOKUP_PAGE_SIZE) {
                         let result = client.lookup_ids(id_chunk, country, lang).await?;
 
                         for result in result.results {
                             match &result {
                                 LookupResult::Software(software) => {
-                                    writer.write_record([
-                                        "software".to_string(),
-                                        software.track_id.to_string(),
-                                        software.bundle_id.to_string(),
-                                        software.artist_id.to_string(),
-                                        software.track_name.to_string(),
-                                        software.artist_name.to_string(),
-                                    ])?;
+                                    writer.write(&LookupRecord::software(software))?;
                                 }
                                 LookupResult::Artist(artist) => {
-                                    writer.write_record([
-                                        "artist".to_string(),
-                                        "".to_string(),
-                                        "".to_string(),
-                                        artist.artist_id.to_string(),
-                                        "".to_string(),
-                                        artist.artist_name.to_string(),
-                                    ])?;
+                                    writer.write(&LookupRecord::artist(artist))?;
                                 }
                             }
                         }
                     }
                 }
+                ApiCommand::LookupIds {
+                    id,
+                    lang,
+                    all_countries: true,
+                    rates,
+                    delay,
+                    ..
+                } => {
+                    let rates = rates
+                        .map(app_store_access::money::Rates::load)
+                        .transpose()?;
+
+                    for country in
+                        app_store_access_apple::request::markets::MarketCode::known_countries()
+                    {
+                        for id_chunk in id.chunks(LOOKUP_PAGE_SIZE) {
+                            let result = client.lookup_ids(id_chunk, country, lang).await?;
+
+                            // Apps that are not available in a market are missing from the results.
+                            for id in id_chunk {
+                                let software =
+                                    result.results.iter().find_map(|result| match result {
+                                        LookupResult::Software(software)
+                                            if software.track_id == *id =>
+                                        {
+                                            Some(software.as_ref())
+                                        }
+                                        _ => None,
+                                    });
+
+                                writer.write(&AvailabilityRecord::from_software(
+                                    *id,
+                                    country,
+                                    software,
+                                    rates.as_ref(),
+                                ))?;
+                            }
+                        }
+
+                        writer.flush()?;
+
+                        tokio::time::sleep(Duration::from_millis(delay)).await;
+                    }
+                }
                 ApiCommand::LookupBundleIds { id, country, lang } => {
                     let ids = id.iter().map(|id| id.as_str()).collect::<Vec<_>>();
 
@@ -192,21 +286,57 @@ async fn main() -> Result<(), Error> {
                         for result in result.results {
                             match &result {
                                 LookupResult::Software(software) => {
-                                    writer.write_record([
-                                        software.track_id.to_string(),
-                                        software.bundle_id.to_string(),
-                                        software.artist_id.to_string(),
-                                        software.track_name.to_string(),
-                                        software.artist_name.to_string(),
-                                    ])?;
+                                    writer.write(&SoftwareRecord::new(software))?;
                                 }
                                 LookupResult::Artist(artist) => {
-                                    log::info!("Unexpected artist result: {}", artist.artist_id,);
+                                    ::log::info!("Unexpected artist result: {}", artist.artist_id,);
                                 }
                             }
                         }
                     }
                 }
+                ApiCommand::Developer { id, country, lang } => {
+                    match client.developer(id, country, lang).await? {
+                        Some(developer) => {
+                            writer.write(&LookupRecord::artist(&developer.artist))?;
+
+                            for software in &developer.apps {
+                                writer.write(&LookupRecord::software(software))?;
+                            }
+                        }
+                        None => {
+                            ::log::warn!("Developer not found: {}", id);
+                        }
+                    }
+                }
+                ApiCommand::GenreTop {
+                    genre,
+                    country,
+                    chart,
+                    subgenres,
+                    delay,
+                } => {
+                    let genre = Genre::from_id(genre).ok_or(Error::UnknownGenre(genre))?;
+                    let genres = if subgenres {
+                        std::iter::once(genre).chain(genre.children()).collect()
+                    } else {
+                        vec![genre]
+                    };
+
+                    for (index, genre) in genres.into_iter().enumerate() {
+                        if index > 0 {
+                            tokio::time::sleep(Duration::from_millis(delay)).await;
+                        }
+
+                        let entries = client.genre_top(genre, country, chart).await?;
+
+                        for (position, entry) in entries.iter().enumerate() {
+                            writer.write(&ChartRecord::new(genre, chart, position, entry))?;
+                        }
+
+                        writer.flush()?;
+                    }
+                }
                 ApiCommand::Reviews {
                     id,
                     country,
@@ -223,19 +353,64 @@ async fn main() -> Result<(), Error> {
 
                     for page in pages {
                         for entry in page.feed.entries() {
-                            writer.write_record([
-                                entry.id.label.to_string(),
-                                entry.link.attributes.href.to_string(),
-                                entry.author.name.label.to_string(),
-                            ])?;
+                            writer.write(&ReviewRecord {
+                                id: entry.id.label,
+                                link: &entry.link.attributes.href,
+                                author: &entry.author.name.label,
+                            })?;
                         }
                     }
                 }
-                ApiCommand::Ratings { id, country } => {
-                    let content = client.ratings_html(id, country).await?;
+                ApiCommand::Ratings { id, country, lang } => {
+                    let content = client.ratings_html(id, country, lang).await?;
 
                     writeln!(std::io::stdout(), "{}", content)?;
                 }
+                ApiCommand::Compatibility { id, country, lang } => {
+                    let result = client.lookup_ids(&[id], country, lang).await?;
+                    let software = result.results.iter().find_map(|result| match result {
+                        LookupResult::Software(software) if software.track_id == id => {
+                            Some(software)
+                        }
+                        _ => None,
+                    });
+
+                    match software {
+                        Some(software) => {
+                            let page = client.app(id, country, Some(lang)).await?;
+                            let app = page.as_ref().and_then(|page| {
+                                page.store_platform_data.product_dv.results.get(&id)
+                            });
+
+                            if app.is_none() {
+                                ::log::warn!("No product page for {}, using lookup data only", id);
+                            }
+
+                            let compatibility = Compatibility::new(software, app);
+
+                            for family in &compatibility.families {
+                                if family.models.is_empty() {
+                                    writer.write(&CompatibilityRecord::new(
+                                        &compatibility,
+                                        family,
+                                        None,
+                                    ))?;
+                                } else {
+                                    for device in &family.models {
+                                        writer.write(&CompatibilityRecord::new(
+                                            &compatibility,
+                                            family,
+                                            Some(device),
+                                        ))?;
+                                    }
+                                }
+                            }
+                        }
+                        None => {
+                            ::log::warn!("App not found: {}", id);
+                        }
+                    }
+                }
                 ApiCommand::Suggest {
                     query,
                     country,
@@ -247,12 +422,12 @@ async fn main() -> Result<(), Error> {
 
                     for country in countries {
                         for suggestion in client.lookup_suggestions(&query, country, lang).await? {
-                            writer.write_record([
-                                query.to_string(),
-                                lang.to_string(),
-                                country.to_string(),
+                            writer.write(&SuggestionRecord {
+                                query: &query,
+                                lang,
+                                country,
                                 suggestion,
-                            ])?;
+                            })?;
                         }
                     }
                 }
@@ -260,16 +435,88 @@ async fn main() -> Result<(), Error> {
 
             writer.flush()?;
         }
+        Command::Crawl { archive, command } => {
+            let client = app_store_access_apple::client::Client::new(Some(archive));
+
+            let mut writer = app_store_access::output::Writer::new(opts.format, std::io::stdout());
+
+            match command {
+                CrawlCommand::Related {
+                    seed,
+                    depth,
+                    country,
+                    lang,
+                    relation,
+                    delay,
+                } => {
+                    let relations = if relation.is_empty() {
+                        vec![
+                            Relation::AlsoLike,
+                            Relation::MoreByDeveloper,
+                            Relation::BundleChild,
+                        ]
+                    } else {
+                        relation
+                    };
+
+                    let mut visited = seed.iter().copied().collect::<HashSet<_>>();
+                    let mut queue = seed.into_iter().map(|id| (id, 0)).collect::<VecDeque<_>>();
+
+                    while let Some((id, current_depth)) = queue.pop_front() {
+                        match client.app(id, country, lang).await? {
+                            Some(page) => {
+                                for related_app in page.related_apps() {
+                                    if relations.contains(&related_app.relation) {
+                                        writer.write(&RelatedAppRecord {
+                                            source: id,
+                                            target: related_app.id,
+                                            relation: related_app.relation,
+                                            depth: current_depth,
+                                        })?;
+
+                                        if current_depth < depth && visited.insert(related_app.id) {
+                                            queue.push_back((related_app.id, current_depth + 1));
+                                        }
+                                    }
+                                }
+                            }
+                            None => {
+                                ::log::warn!("App not found: {}", id);
+                            }
+                        }
+
+                        writer.flush()?;
+
+                        if !queue.is_empty() {
+                            tokio::time::sleep(Duration::from_millis(delay)).await;
+                        }
+                    }
+
+                    ::log::info!("Visited {} apps", visited.len());
+                }
+            }
+        }
+        Command::Genres => {
+            let mut writer = app_store_access::output::Writer::new(opts.format, std::io::stdout());
+
+            for (genre, id, name) in Genre::values() {
+                writer.write(&GenreRecord {
+                    id,
+                    name,
+                    parent_id: genre.parent().map(|parent| parent.id()),
+                })?;
+            }
+
+            writer.flush()?;
+        }
         Command::Store {
             archive,
             most_recent_first,
             command,
         } => {
-            let store = scraper_trail::archive::store::Store::new(archive);
+            let store = scraper_trail::archive::store::Store::new(archive.clone());
 
-            let mut writer = csv::WriterBuilder::new()
-                .has_headers(false)
-                .from_writer(std::io::stdout());
+            let mut writer = app_store_access::output::Writer::new(opts.format, std::io::stdout());
 
             match command {
                 StoreCommand::Apps => {
@@ -283,23 +530,15 @@ async fn main() -> Result<(), Error> {
                                 if let Some(app) =
                                     page.store_platform_data.product_dv.results.get(&id)
                                 {
-                                    writer.write_record([
-                                        id.to_string(),
-                                        app.common
-                                            .bundle_id
-                                            .as_ref()
-                                            .map(|id| id.to_string())
-                                            .unwrap_or_default(),
-                                        app.common.name.to_string(),
-                                    ])?;
+                                    writer.write(&AppRecord::new(id, app))?;
                                 } else {
-                                    log::warn!(
+                                    ::log::warn!(
                                         "Empty app result: {}",
                                         path.as_os_str().to_string_lossy()
                                     );
                                 }
                             } else {
-                                log::warn!(
+                                ::log::warn!(
                                     "Unexpected request: {}",
                                     path.as_os_str().to_string_lossy()
                                 );
@@ -308,6 +547,69 @@ async fn main() -> Result<(), Error> {
                     }
                 }
                 StoreCommand::Search => {}
+                StoreCommand::Suggestions => {
+                    for (path, entry) in store.entries::<Data<'static>>(most_recent_first)? {
+                        let entry =
+                            entry.map_err(|error| Error::from_scraper_store_error(path, error))?;
+
+                        if let (
+                            Data::Suggestions(list),
+                            Request::Suggest {
+                                term,
+                                country,
+                                language,
+                            },
+                        ) = (entry.exchange.response.data, &entry.request_params)
+                        {
+                            for hint in list.hints {
+                                writer.write(&ArchivedSuggestionRecord {
+                                    query: term,
+                                    lang: *language,
+                                    country: *country,
+                                    suggestion: &hint.term,
+                                })?;
+                            }
+                        }
+                    }
+                }
+
+                StoreCommand::Index { command } => match command {
+                    IndexCommand::Build { index } => {
+                        let mut index = app_store_access::index::Index::open(index)?;
+                        let stats = app_store_access_apple::index::build(&archive, &mut index)?;
+
+                        ::log::info!(
+                            "Indexed {} new files ({} already indexed, {} failed)",
+                            stats.ingested,
+                            stats.skipped,
+                            stats.failed
+                        );
+                    }
+                    IndexCommand::Query { index, sql } => {
+                        let index = app_store_access::index::Index::open(index)?;
+                        let (columns, rows) = index.query(&sql)?;
+
+                        for row in rows {
+                            writer.write_row(&columns, &row)?;
+                        }
+                    }
+                },
+                StoreCommand::Export { output } => {
+                    let stats = app_store_access_apple::export::export(
+                        &archive,
+                        output,
+                        most_recent_first,
+                    )?;
+
+                    ::log::info!(
+                        "Exported {} apps, {} developers, {} reviews, {} search results ({} files failed)",
+                        stats.apps,
+                        stats.developers,
+                        stats.reviews,
+                        stats.search_results,
+                        stats.failed
+                    );
+                }
 
                 #[cfg(not(feature = "strict"))]
                 StoreCommand::FixStrict => {
@@ -346,27 +648,32 @@ async fn main() -> Result<(), Error> {
                     for fix in fixes {
                         match fix {
                             Fix::UnknownContentRatingAdvisory(value) => {
-                                writer.write_record(["content_rating_advisory", &value, "", ""])?;
+                                writer.write(&FixRecord::ContentRatingAdvisory {
+                                    kind: "content_rating_advisory",
+                                    value: &value,
+                                    expected_id: None,
+                                    found_id: None,
+                                })?;
                             }
                             Fix::InvalidGenreName {
                                 name,
                                 found_id,
                                 expected_id,
                             } => {
-                                writer.write_record([
-                                    "genre_name",
-                                    expected_id.to_string().as_str(),
-                                    found_id.to_string().as_str(),
-                                    &name,
-                                ])?;
+                                writer.write(&FixRecord::GenreName {
+                                    kind: "genre_name",
+                                    expected_id,
+                                    found_id: Some(found_id),
+                                    value: &name,
+                                })?;
                             }
                             Fix::UnknownGenreName { name, expected_id } => {
-                                writer.write_record([
-                                    "genre_name",
-                                    expected_id.to_string().as_str(),
-                                    "",
-                                    &name,
-                                ])?;
+                                writer.write(&FixRecord::GenreName {
+                                    kind: "genre_name",
+                                    expected_id,
+                                    found_id: None,
+                                    value: &name,
+                                })?;
                             }
                         }
                     }
@@ -385,6 +692,9 @@ async fn main() -> Result<(), Error> {
 struct Opts {
     #[clap(flatten)]
     verbose: Verbosity,
+    /// Output format: csv, csv-with-headers, or jsonl
+    #[clap(long, global = true, default_value = "csv")]
+    format: app_store_access::output::Format,
     #[clap(subcommand)]
     command: Command,
 }
@@ -398,6 +708,15 @@ enum Command {
         #[clap(subcommand)]
         command: ApiCommand,
     },
+    /// Crawl the store by following links between apps (responses are archived)
+    Crawl {
+        #[clap(long, default_value = "data/apple/")]
+        archive: PathBuf,
+        #[clap(subcommand)]
+        command: CrawlCommand,
+    },
+    /// Print the genre hierarchy
+    Genres,
     /// Read data from the local store
     Store {
         #[clap(long)]
@@ -417,6 +736,18 @@ enum ApiCommand {
         id: u64,
         #[clap(long, default_value = "us")]
         country: Country,
+        /// Request the listing in this language (if supported by the market)
+        #[clap(long)]
+        lang: Option<Language>,
+        /// Request the app in every known market and print an availability and price matrix
+        #[clap(long, conflicts_with = "country")]
+        all_countries: bool,
+        /// JSON file of exchange rates used to add prices in a reference currency
+        #[clap(long, requires = "all_countries")]
+        rates: Option<PathBuf>,
+        /// Time to wait between markets in milliseconds
+        #[clap(long, default_value = "500")]
+        delay: u64,
     },
     /// Perform a search for a given query string
     Search {
@@ -441,6 +772,15 @@ enum ApiCommand {
         country: Country,
         #[clap(long, default_value = "en")]
         lang: Language,
+        /// Look up the apps in every known market and print an availability and price matrix
+        #[clap(long, conflicts_with = "country")]
+        all_countries: bool,
+        /// JSON file of exchange rates used to add prices in a reference currency
+        #[clap(long, requires = "all_countries")]
+        rates: Option<PathBuf>,
+        /// Time to wait between markets in milliseconds
+        #[clap(long, default_value = "500")]
+        delay: u64,
     },
     /// Look up apps by bundle ID (option can be provided multiple times)
     LookupBundleIds {
@@ -451,6 +791,31 @@ enum ApiCommand {
         #[clap(long, default_value = "en")]
         lang: Language,
     },
+    /// Look up a developer and all of their apps by developer (artist) ID
+    Developer {
+        #[clap(long)]
+        id: u64,
+        #[clap(long, default_value = "us")]
+        country: Country,
+        #[clap(long, default_value = "en")]
+        lang: Language,
+    },
+    /// List the top chart for a genre by ID (for example 7012 for puzzle games)
+    GenreTop {
+        #[clap(long, default_value = "0")]
+        genre: u16,
+        #[clap(long, default_value = "us")]
+        country: Country,
+        /// Chart type: top-free, top-paid, or top-grossing
+        #[clap(long, default_value = "top-free")]
+        chart: ChartType,
+        /// Also list the charts for each subgenre of the genre
+        #[clap(long)]
+        subgenres: bool,
+        /// Time to wait between charts in milliseconds
+        #[clap(long, default_value = "500")]
+        delay: u64,
+    },
     /// Look up reviews for an app by ID
     Reviews {
         #[clap(long)]
@@ -468,6 +833,17 @@ enum ApiCommand {
         id: u64,
         #[clap(long, default_value = "us")]
         country: Country,
+        #[clap(long)]
+        lang: Option<Language>,
+    },
+    /// Print the device families, models, minimum OS version, and download sizes for an app
+    Compatibility {
+        #[clap(long)]
+        id: u64,
+        #[clap(long, default_value = "us")]
+        country: Country,
+        #[clap(long, default_value = "en")]
+        lang: Language,
     },
     /// Look up autocomplete suggestions for a given query string
     Suggest {
@@ -481,10 +857,332 @@ enum ApiCommand {
     },
 }
 
+#[derive(Debug, Parser)]
+enum CrawlCommand {
+    /// Breadth-first crawl of related apps from product pages, printing one row per link
+    Related {
+        /// App ID to start from (option can be provided multiple times)
+        #[clap(long, required = true)]
+        seed: Vec<u64>,
+        /// Maximum number of links to follow from a seed
+        #[clap(long, default_value = "1")]
+        depth: usize,
+        #[clap(long, default_value = "us")]
+        country: Country,
+        #[clap(long)]
+        lang: Option<Language>,
+        /// Relations to follow: also-like, more-by-developer, bundle-child, or top-app (option
+        /// can be provided multiple times; defaults to all except top-app)
+        #[clap(long)]
+        relation: Vec<Relation>,
+        /// Time to wait between apps in milliseconds
+        #[clap(long, default_value = "500")]
+        delay: u64,
+    },
+}
+
 #[derive(Debug, Parser)]
 enum StoreCommand {
     Apps,
     Search,
+    /// Print archived autocomplete suggestions
+    Suggestions,
+    /// Maintain and query a local SQLite index of the store
+    Index {
+        #[clap(subcommand)]
+        command: IndexCommand,
+    },
+    /// Export apps, developers, reviews, and search results as Parquet files
+    Export {
+        #[clap(long, default_value = "data/export/apple/")]
+        output: PathBuf,
+    },
     #[cfg(not(feature = "strict"))]
     FixStrict,
 }
+
+#[derive(Debug, Parser)]
+enum IndexCommand {
+    /// Add any archive files that have not already been indexed
+    Build {
+        #[clap(long, default_value = "data/apple.db")]
+        index: PathBuf,
+    },
+    /// Run a SQL query against the index and print the results
+    Query {
+        #[clap(long, default_value = "data/apple.db")]
+        index: PathBuf,
+        #[clap(long)]
+        sql: String,
+    },
+}
+
+#[derive(serde::Serialize)]
+struct CompatibilityRecord<'a> {
+    app_id: u64,
+    family: &'static str,
+    designed_for: Option<bool>,
+    minimum_os_version: &'a str,
+    min_download_size: Option<usize>,
+    max_download_size: Option<usize>,
+    device: Option<&'a str>,
+}
+
+impl<'a> CompatibilityRecord<'a> {
+    fn new(compatibility: &'a Compatibility, family: &Family, device: Option<&'a Device>) -> Self {
+        Self {
+            app_id: compatibility.app_id,
+            family: family.family.as_str(),
+            designed_for: family.designed_for,
+            minimum_os_version: &compatibility.minimum_os_version,
+            min_download_size: family.download_size.map(|size| size.min),
+            max_download_size: family.download_size.map(|size| size.max),
+            device: device.map(Device::as_str),
+        }
+    }
+}
+
+#[derive(serde::Serialize)]
+struct AppRecord<'a> {
+    id: u64,
+    bundle_id: Option<&'a str>,
+    name: &'a str,
+}
+
+impl<'a> AppRecord<'a> {
+    fn new(id: u64, app: &'a ProductDvResult<'_>) -> Self {
+        Self {
+            id,
+            bundle_id: app.common.bundle_id.as_deref(),
+            name: &app.common.name,
+        }
+    }
+}
+
+/// The availability of an app in a single market, with its localized title, price, and rating.
+#[derive(serde::Serialize)]
+struct AvailabilityRecord<'a> {
+    id: u64,
+    #[serde(with = "app_store_access::country::country_code")]
+    country: Country,
+    available: bool,
+    title: Option<&'a str>,
+    price: Option<f64>,
+    currency: Option<app_store_access::money::CurrencyCode>,
+    price_formatted: Option<&'a str>,
+    reference_price: Option<app_store_access::money::Amount>,
+    reference_currency: Option<app_store_access::money::CurrencyCode>,
+    rating: Option<f64>,
+    rating_count: Option<u64>,
+}
+
+impl<'a> AvailabilityRecord<'a> {
+    fn new(
+        id: u64,
+        country: Country,
+        app: Option<&'a ProductDvResult<'_>>,
+        currency: Option<app_store_access::money::CurrencyCode>,
+        rates: Option<&app_store_access::money::Rates>,
+    ) -> Self {
+        let offer = app.and_then(|app| app.common.offers.first());
+        let money = offer
+            .zip(currency)
+            .and_then(|(offer, currency)| offer.money(currency));
+        let reference = money
+            .zip(rates)
+            .and_then(|(money, rates)| money.convert(rates));
+
+        Self {
+            id,
+            country,
+            available: app.is_some(),
+            title: app.map(|app| app.common.name.as_ref()),
+            price: offer
+                .and_then(app_store_access_apple::model::offer::Offer::amount)
+                .map(app_store_access::money::Amount::to_f64),
+            currency: money.map(|money| money.currency),
+            price_formatted: offer.map(|offer| offer.price_formatted.as_ref()),
+            reference_price: reference.map(|money| money.amount),
+            reference_currency: reference.map(|money| money.currency),
+            rating: app.and_then(|app| app.common.user_rating.value.to_f64()),
+            rating_count: app.map(|app| u64::from(app.common.user_rating.rating_count)),
+        }
+    }
+
+    fn from_software(
+        id: u64,
+        country: Country,
+        software: Option<&'a Software<'_>>,
+        rates: Option<&app_store_access::money::Rates>,
+    ) -> Self {
+        let money = software.and_then(Software::money);
+        let reference = money
+            .zip(rates)
+            .and_then(|(money, rates)| money.convert(rates));
+
+        Self {
+            id,
+            country,
+            available: software.is_some(),
+            title: software.map(|software| software.track_name.as_ref()),
+            price: money.map(|money| money.amount.to_f64()),
+            currency: money.map(|money| money.currency),
+            price_formatted: software.and_then(|software| software.formatted_price.as_deref()),
+            reference_price: reference.map(|money| money.amount),
+            reference_currency: reference.map(|money| money.currency),
+            rating: software.and_then(|software| software.average_user_rating.to_f64()),
+            rating_count: software
+                .and_then(|software| u64::try_from(software.user_rating_count).ok()),
+        }
+    }
+}
+
+#[derive(serde::Serialize)]
+struct SoftwareRecord<'a> {
+    track_id: u64,
+    bundle_id: &'a str,
+    artist_id: u32,
+    track_name: &'a str,
+    artist_name: &'a str,
+}
+
+impl<'a> SoftwareRecord<'a> {
+    fn new(software: &'a Software<'_>) -> Self {
+        Self {
+            track_id: software.track_id,
+            bundle_id: &software.bundle_id,
+            artist_id: software.artist_id,
+            track_name: &software.track_name,
+            artist_name: &software.artist_name,
+        }
+    }
+}
+
+#[derive(serde::Serialize)]
+struct LookupRecord<'a> {
+    wrapper_type: &'static str,
+    track_id: Option<u64>,
+    bundle_id: Option<&'a str>,
+    artist_id: u64,
+    track_name: Option<&'a str>,
+    artist_name: &'a str,
+}
+
+impl<'a> LookupRecord<'a> {
+    fn software(software: &'a Software<'_>) -> Self {
+        Self {
+            wrapper_type: "software",
+            track_id: Some(software.track_id),
+            bundle_id: Some(&software.bundle_id),
+            artist_id: software.artist_id.into(),
+            track_name: Some(&software.track_name),
+            artist_name: &software.artist_name,
+        }
+    }
+
+    fn artist(artist: &'a Artist<'_>) -> Self {
+        Self {
+            wrapper_type: "artist",
+            track_id: None,
+            bundle_id: None,
+            artist_id: artist.artist_id,
+            track_name: None,
+            artist_name: &artist.artist_name,
+        }
+    }
+}
+
+#[derive(serde::Serialize)]
+struct ChartRecord<'a> {
+    genre_id: u16,
+    chart: ChartType,
+    position: usize,
+    app_id: u64,
+    bundle_id: Option<&'a str>,
+    name: &'a str,
+    artist_id: Option<u64>,
+    artist_name: Option<&'a str>,
+    app_genre_id: Option<u16>,
+    price: Option<&'a str>,
+    currency: Option<&'a str>,
+}
+
+impl<'a> ChartRecord<'a> {
+    fn new(genre: Genre, chart: ChartType, position: usize, entry: &'a chart::Entry) -> Self {
+        Self {
+            genre_id: genre.id(),
+            chart,
+            position,
+            app_id: entry.id,
+            bundle_id: entry.bundle_id.as_deref(),
+            name: &entry.name,
+            artist_id: entry.artist_id,
+            artist_name: entry.artist_name.as_deref(),
+            app_genre_id: entry.genre.map(|genre| genre.id()),
+            price: entry.price.as_deref(),
+            currency: entry.currency.as_deref(),
+        }
+    }
+}
+
+#[derive(serde::Serialize)]
+struct RelatedAppRecord {
+    source: u64,
+    target: u64,
+    relation: Relation,
+    depth: usize,
+}
+
+#[derive(serde::Serialize)]
+struct GenreRecord {
+    id: u16,
+    name: &'static str,
+    parent_id: Option<u16>,
+}
+
+#[derive(serde::Serialize)]
+struct ReviewRecord<'a> {
+    id: u64,
+    link: &'a str,
+    author: &'a str,
+}
+
+#[derive(serde::Serialize)]
+struct SuggestionRecord<'a> {
+    query: &'a str,
+    #[serde(with = "app_store_access::language::language_code")]
+    lang: Language,
+    #[serde(with = "app_store_access::country::country_code")]
+    country: Country,
+    suggestion: String,
+}
+
+/// Suggestions archived before requests were localized do not have a language.
+#[derive(serde::Serialize)]
+struct ArchivedSuggestionRecord<'a> {
+    query: &'a str,
+    #[serde(with = "app_store_access::language::language_code_option")]
+    lang: Option<Language>,
+    #[serde(with = "app_store_access::country::country_code")]
+    country: Country,
+    suggestion: &'a str,
+}
+
+/// Content rating advisory rows have the value in the second column (with empty ID columns).
+#[cfg(not(feature = "strict"))]
+#[derive(serde::Serialize)]
+#[serde(untagged)]
+enum FixRecord<'a> {
+    ContentRatingAdvisory {
+        kind: &'static str,
+        value: &'a str,
+        expected_id: Option<u16>,
+        found_id: Option<u16>,
+    },
+    GenreName {
+        kind: &'static str,
+        expected_id: u16,
+        found_id: Option<u16>,
+        value: &'a str,
+    },
+}
@@ -2,7 +2,6 @@ use crate::request::params::review::SortOrder;
 use app_store_access::{country::Country, language::Language};
 use bounded_static_derive_more::ToStatic;
 use scraper_trail::client::{json_send, text_send};
-use scraper_trail::request::params::Params;
 use serde_json::Value;
 use std::borrow::Cow;
 use std::path::{Path, PathBuf};
@@ -21,6 +20,8 @@ pub enum Error {
     ScraperClient(#[from] scraper_trail::client::Error),
     #[error("Header value error")]
     RequestHeaderValue(#[from] reqwest::header::InvalidHeaderValue),
+    #[error("Market error")]
+    Market(#[from] crate::request::markets::Error),
 }
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq, ToStatic)]
@@ -65,9 +66,10 @@ impl Client {
         &self,
         id: u64,
         country: Country,
+        language: Option<Language>,
     ) -> Result<Option<crate::model::full::Page<'_>>, Error> {
-        let request = crate::request::Request::app(id, country);
-        let exchange = text_send(&self.underlying, request.build_request(None)).await?;
+        let request = crate::request::Request::app(id, country, language)?;
+        let exchange = text_send(&self.underlying, request.try_build_request(None)?).await?;
 
         if Self::is_not_found_body(&exchange.response.data) {
             Ok(None)
@@ -90,8 +92,8 @@ impl Client {
         country: Country,
         language: Language,
     ) -> Result<super::model::search::Page<'_>, Error> {
-        let request = crate::request::Request::search(query, country, language);
-        let exchange = json_send(&self.underlying, request.build_request(None)).await?;
+        let request = crate::request::Request::search(query, country, language)?;
+        let exchange = json_send(&self.underlying, request.try_build_request(None)?).await?;
 
         if let Some(output) = &self.output {
             exchange.save_file(output)?;
@@ -107,7 +109,7 @@ impl Client {
         language: Language,
     ) -> Result<super::model::lookup::LookupResultList<'_>, Error> {
         let request = crate::request::Request::lookup_ids(ids.iter().copied(), country, language);
-        let exchange = json_send(&self.underlying, request.build_request(None)).await?;
+        let exchange = json_send(&self.underlying, request.try_build_request(None)?).await?;
 
         if let Some(output) = &self.output {
             exchange.save_file(output)?;
@@ -124,7 +126,7 @@ impl Client {
     ) -> Result<super::model::lookup::LookupResultList<'_>, Error> {
         let request =
             crate::request::Request::lookup_bundle_ids(ids.iter().copied(), country, language);
-        let exchange = json_send(&self.underlying, request.build_request(None)).await?;
+        let exchange = json_send(&self.underlying, request.try_build_request(None)?).await?;
 
         if let Some(output) = &self.output {
             exchange.save_file(output)?;
@@ -133,6 +135,48 @@ impl Client {
         Ok(serde_json::from_value(exchange.response.data)?)
     }
 
+    /// Look up a developer (artist) and all of their apps.
+    ///
+    /// Returns `None` if the ID is not a developer ID.
+    pub async fn developer(
+        &self,
+        artist_id: u64,
+        country: Country,
+        language: Language,
+    ) -> Result<Option<super::model::lookup::Developer<'_>>, Error> {
+        let request = crate::request::Request::developer(artist_id, country, language);
+        let exchange = json_send(&self.underlying, request.try_build_request(None)?).await?;
+
+        if let Some(output) = &self.output {
+            exchange.save_file(output)?;
+        }
+
+        let list = serde_json::from_value::<super::model::lookup::LookupResultList<'_>>(
+            exchange.response.data,
+        )?;
+
+        Ok(list.into_developer(artist_id))
+    }
+
+    /// List the apps in a genre's top chart, in chart order.
+    pub async fn genre_top(
+        &self,
+        genre: crate::model::genre::Genre,
+        country: Country,
+        chart: crate::request::params::chart::ChartType,
+    ) -> Result<Vec<super::model::chart::Entry>, Error> {
+        let request = crate::request::Request::genre_top(genre, country, chart);
+        let exchange = json_send(&self.underlying, request.try_build_request(None)?).await?;
+
+        if let Some(output) = &self.output {
+            exchange.save_file(output)?;
+        }
+
+        let chart = serde_json::from_value::<super::model::chart::Chart>(exchange.response.data)?;
+
+        Ok(chart.entries)
+    }
+
     pub async fn reviews(
         &self,
         id: u64,
@@ -141,7 +185,7 @@ impl Client {
         page: usize,
     ) -> Result<Vec<super::model::reviews::Page<'_>>, Error> {
         let request = crate::request::Request::reviews(id, country, sort, page);
-        let exchange = json_send(&self.underlying, request.build_request(None)).await?;
+        let exchange = json_send(&self.underlying, request.try_build_request(None)?).await?;
 
         if let Some(output) = &self.output {
             exchange.save_file(output)?;
@@ -157,7 +201,8 @@ impl Client {
         if let Some(last_page) = last_page {
             for next in 2..=last_page {
                 let request = crate::request::Request::reviews(id, country, sort, next);
-                let exchange = json_send(&self.underlying, request.build_request(None)).await?;
+                let exchange =
+                    json_send(&self.underlying, request.try_build_request(None)?).await?;
 
                 if let Some(output) = &self.output {
                     exchange.save_file(output)?;
@@ -175,9 +220,14 @@ impl Client {
     }
 
     // TODO: Parse ratings HTML.
-    pub async fn ratings_html(&self, id: u64, country: Country) -> Result<String, Error> {
-        let request = crate::request::Request::ratings(id, country);
-        let exchange = text_send(&self.underlying, request.build_request(None)).await?;
+    pub async fn ratings_html(
+        &self,
+        id: u64,
+        country: Country,
+        language: Option<Language>,
+    ) -> Result<String, Error> {
+        let request = crate::request::Request::ratings(id, country, language)?;
+        let exchange = text_send(&self.underlying, request.try_build_request(None)?).await?;
 
         Ok(exchange.response.data)
     }
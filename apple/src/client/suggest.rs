@@ -1,6 +1,4 @@
-use crate::request::markets::MarketCode;
-
-const SUGGEST_URL: &str = "https://search.itunes.apple.com/WebObjects/MZSearchHints.woa/wa/hints?clientApplication=Software&term=";
+use scraper_trail::client::text_send;
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -8,6 +6,14 @@ pub enum Error {
     Reqwest(#[from] reqwest::Error),
     #[error("PList deserialization error")]
     Plist(#[from] plist::Error),
+    #[error("I/O error")]
+    Io(#[from] std::io::Error),
+    #[error("JSON error")]
+    Json(#[from] serde_json::Error),
+    #[error("Scraper client error")]
+    ScraperClient(#[from] scraper_trail::client::Error),
+    #[error("Market error")]
+    Market(#[from] crate::request::markets::Error),
 }
 
 impl app_store_access::client::SuggestionClient for super::Client {
@@ -17,28 +23,29 @@ impl app_store_access::client::SuggestionClient for super::Client {
         &self,
         term: &str,
         country: app_store_access::country::Country,
-        _lang: app_store_access::language::Language,
+        lang: app_store_access::language::Language,
     ) -> impl std::future::Future<Output = Result<Vec<String>, Self::Error>> + Send {
-        let url = format!("{SUGGEST_URL}{}", urlencoding::encode(term));
-        let store_id: MarketCode = country.into();
+        let request = crate::request::Request::suggest(term, country, Some(lang));
 
         async move {
-            let response = self
-                .underlying
-                .get(&url)
-                .header("X-Apple-Store-Front", format!("{store_id},29"))
-                .send()
-                .await?;
-
-            let bytes = response.bytes().await?;
-
-            let hints = plist::from_bytes::<crate::model::suggest::HintList>(&bytes)?;
-
-            Ok(hints
+            let exchange = text_send(&self.underlying, request?.try_build_request(None)?).await?;
+            let hints = plist::from_bytes::<crate::model::suggest::HintList>(
+                exchange.response.data.as_bytes(),
+            )?;
+            let json = serde_json::to_value(&hints)?;
+            let terms = hints
                 .hints
                 .into_iter()
                 .map(|hint| hint.term.into_owned())
-                .collect())
+                .collect();
+
+            let new_exchange = exchange.map(|_| json);
+
+            if let Some(output) = &self.output {
+                new_exchange.save_file(output)?;
+            }
+
+            Ok(terms)
         }
     }
 }
@@ -0,0 +1,240 @@
+//! Which devices and OS versions can run an app.
+//!
+//! The lookup API lists the supported device models, and the product page lists the device
+//! families the app runs on (and those it was designed for), together with download sizes. This
+//! module combines them into a matrix of device family → models → minimum OS → download size.
+use crate::model::{DeviceFamily, devices::Device, full::ProductDvResult, lookup::Software};
+
+/// The smallest and largest download sizes (in bytes) for a device family.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SizeRange {
+    pub min: usize,
+    pub max: usize,
+}
+
+impl SizeRange {
+    fn from_sizes<I: IntoIterator<Item = usize>>(sizes: I) -> Option<Self> {
+        let sizes = sizes.into_iter().collect::<Vec<_>>();
+
+        Some(Self {
+            min: *sizes.iter().min()?,
+            max: *sizes.iter().max()?,
+        })
+    }
+}
+
+/// Support for a single device family.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Family {
+    pub family: DeviceFamily,
+    /// Whether the app was designed for the family, rather than running in compatibility mode
+    /// (for example an iPhone app on an iPad or a Mac). Only available with a product page.
+    pub designed_for: Option<bool>,
+    /// Supported models, in the order they are listed by the lookup API.
+    pub models: Vec<Device>,
+    pub download_size: Option<SizeRange>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Compatibility {
+    pub app_id: u64,
+    /// The store only reports a single minimum version (for the app's primary platform).
+    pub minimum_os_version: String,
+    pub families: Vec<Family>,
+}
+
+impl Compatibility {
+    /// Combine a lookup result with the product page for the same app (if available).
+    ///
+    /// Download sizes are keyed by hardware identifier (for example `iPhone15,2`) on the product
+    /// page, so they are only reported per family. Without a product page (or if the page has no
+    /// size for a family), the single size from the lookup API is used.
+    #[must_use]
+    pub fn new(software: &Software<'_>, app: Option<&ProductDvResult<'_>>) -> Self {
+        let devices = software.supported_devices.as_deref().unwrap_or_default();
+
+        let mut families = app
+            .map(|app| app.common.device_families.clone())
+            .unwrap_or_default();
+
+        for family in devices.iter().filter_map(Device::family) {
+            if !families.contains(&family) {
+                families.push(family);
+            }
+        }
+
+        let sizes = app
+            .and_then(|app| app.file_size_by_device.as_ref())
+            .map(|sizes| {
+                sizes
+                    .iter()
+                    .map(|(key, size)| (DeviceFamily::from_identifier(key), *size))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        let families = families
+            .into_iter()
+            .map(|family| Family {
+                family,
+                designed_for: app.map(|app| {
+                    app.common
+                        .designed_for_device_families
+                        .as_ref()
+                        .is_none_or(|designed_for| designed_for.contains(&family))
+                }),
+                models: devices
+                    .iter()
+                    .filter(|device| device.family() == Some(family))
+                    .cloned()
+                    .collect(),
+                download_size: SizeRange::from_sizes(
+                    sizes
+                        .iter()
+                        .filter(|(key_family, _)| *key_family == Some(family))
+                        .map(|(_, size)| *size),
+                )
+                .or_else(|| {
+                    SizeRange::from_sizes(
+                        sizes
+                            .iter()
+                            .filter(|(key_family, _)| key_family.is_none())
+                            .map(|(_, size)| *size),
+                    )
+                })
+                .or_else(|| SizeRange::from_sizes([software.file_size_bytes])),
+            })
+            .collect();
+
+        Self {
+            app_id: software.track_id,
+            minimum_os_version: app.map_or_else(
+                || software.minimum_os_version.to_string(),
+                |app| app.common.minimum_os_version.to_string(),
+            ),
+            families,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn family_from_identifier() {
+        let cases = [
+            ("iPhone15-iPhone15", Some(DeviceFamily::Iphone)),
+            ("iPhone15,2", Some(DeviceFamily::Iphone)),
+            ("iPadAir11M2-iPadAir11M2", Some(DeviceFamily::Ipad)),
+            (
+                "iPodTouchSeventhGen-iPodTouchSeventhGen",
+                Some(DeviceFamily::Ipod),
+            ),
+            (
+                "WatchSESecondGen-WatchSESecondGen",
+                Some(DeviceFamily::Watch),
+            ),
+            ("AppleTV4-AppleTV4", Some(DeviceFamily::Tvos)),
+            (
+                "AppleVisionPro-AppleVisionPro",
+                Some(DeviceFamily::RealityDevice),
+            ),
+            ("RealityDevice14,1", Some(DeviceFamily::RealityDevice)),
+            ("MacDesktop-MacDesktop", Some(DeviceFamily::Mac)),
+            ("universal", None),
+        ];
+
+        for (identifier, expected) in cases {
+            assert_eq!(
+                DeviceFamily::from_identifier(identifier),
+                expected,
+                "{identifier}"
+            );
+        }
+    }
+
+    #[test]
+    fn compatibility_from_lookup() {
+        let json = r#"{
+            "screenshotUrls": [],
+            "artistViewUrl": "https://apps.apple.com/us/developer/example/id456",
+            "artworkUrl60": "https://example.com/60.png",
+            "artworkUrl100": "https://example.com/100.png",
+            "artworkUrl512": "https://example.com/512.png",
+            "supportedDevices": [
+                "iPhone5s-iPhone5s",
+                "iPadAir-iPadAir",
+                "iPhone4S-iPhone4S",
+                "AppleTV4-AppleTV4"
+            ],
+            "kind": "software",
+            "languageCodesISO2A": ["EN"],
+            "fileSizeBytes": "1000",
+            "trackContentRating": "4+",
+            "trackCensoredName": "Example",
+            "trackViewUrl": "https://apps.apple.com/us/app/example/id123",
+            "contentAdvisoryRating": "4+",
+            "artistId": 456,
+            "artistName": "Example",
+            "isVppDeviceBasedLicensingEnabled": true,
+            "sellerName": "Example",
+            "bundleId": "com.example",
+            "trackId": 123,
+            "currentVersionReleaseDate": "2024-01-01T00:00:00Z",
+            "trackName": "Example",
+            "releaseDate": "2020-01-01T00:00:00Z",
+            "version": "1.0",
+            "currency": "USD",
+            "description": "An example app",
+            "minimumOsVersion": "15.0",
+            "primaryGenreId": 6014,
+            "primaryGenreName": "Games",
+            "genreIds": ["6014"],
+            "genres": ["Games"],
+            "price": 0,
+            "userRatingCount": 0,
+            "userRatingCountForCurrentVersion": 0,
+            "averageUserRating": 0,
+            "averageUserRatingForCurrentVersion": 0
+        }"#;
+
+        let software: Software<'_> = serde_json::from_str(json).unwrap();
+        let compatibility = Compatibility::new(&software, None);
+
+        assert_eq!(compatibility.app_id, 123);
+        assert_eq!(compatibility.minimum_os_version, "15.0");
+        assert_eq!(
+            compatibility.families,
+            vec![
+                Family {
+                    family: DeviceFamily::Iphone,
+                    designed_for: None,
+                    models: vec![Device::IPhone5sIPhone5s, Device::IPhone4SIPhone4S],
+                    download_size: Some(SizeRange {
+                        min: 1000,
+                        max: 1000
+                    }),
+                },
+                Family {
+                    family: DeviceFamily::Ipad,
+                    designed_for: None,
+                    models: vec![Device::IPadAirIPadAir],
+                    download_size: Some(SizeRange {
+                        min: 1000,
+                        max: 1000
+                    }),
+                },
+                Family {
+                    family: DeviceFamily::Tvos,
+                    designed_for: None,
+                    models: vec![Device::AppleTv4AppleTv4],
+                    download_size: Some(SizeRange {
+                        min: 1000,
+                        max: 1000
+                    }),
+                },
+            ]
+        );
+    }
+}
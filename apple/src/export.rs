@@ -0,0 +1,405 @@
+use crate::{
+    archive::Data,
+    model::{full::ProductDvResult, lookup::LookupResult, lookup::Software, reviews},
+};
+use app_store_access::export::{
+    Record, Snapshot, Stats, Writer, bool_column, bool_field, f64_column, f64_field,
+    snapshot_columns, snapshot_schema, string_column, string_field, timestamp_column,
+    timestamp_field, u64_column, u64_field,
+};
+use arrow::array::ArrayRef;
+use arrow::datatypes::SchemaRef;
+use chrono::{DateTime, Utc};
+use num_traits::ToPrimitive;
+use std::path::Path;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Export error")]
+    Export(#[from] app_store_access::export::Error),
+    #[error("I/O error")]
+    Io(#[from] std::io::Error),
+}
+
+/// Export all archived apps, developers, reviews, and search results as Parquet files in the
+/// given output directory.
+///
+/// As when building an index, archive files that cannot be read or parsed are logged and skipped.
+pub fn export<P: AsRef<Path>, Q: AsRef<Path>>(
+    archive: P,
+    output: Q,
+    most_recent_first: bool,
+) -> Result<Stats, Error> {
+    let output = output.as_ref();
+    std::fs::create_dir_all(output)?;
+
+    let mut apps = Writer::<AppRecord>::create(output.join("apps.parquet"))?;
+    let mut developers = Writer::<DeveloperRecord>::create(output.join("developers.parquet"))?;
+    let mut reviews = Writer::<ReviewRecord>::create(output.join("reviews.parquet"))?;
+    let mut search_results =
+        Writer::<SearchResultRecord>::create(output.join("search_results.parquet"))?;
+
+    let store = scraper_trail::archive::store::Store::new(archive.as_ref());
+    let mut failed = 0;
+
+    for (path, entry) in store.entries::<Data>(most_recent_first)? {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(error) => {
+                log::warn!("Skipping archive file {}: {error:?}", path.display());
+                failed += 1;
+                continue;
+            }
+        };
+        let request = &entry.request_params;
+
+        let snapshot = Snapshot {
+            timestamp: entry.exchange.request.timestamp,
+            country: Some(request.country()),
+            language: request.language(),
+        };
+
+        match entry.exchange.response.data {
+            Data::App(page) => {
+                for app in page.store_platform_data.product_dv.results.values() {
+                    apps.write(AppRecord::from_product_dv(snapshot, app))?;
+                    developers.write(DeveloperRecord {
+                        snapshot,
+                        developer_id: app.artist_id,
+                        name: Some(app.common.artist_name.to_string()),
+                        app_id: Some(app.id),
+                    })?;
+                }
+            }
+            Data::Search(page) => {
+                let ids = page
+                    .bubbles
+                    .bubbles()
+                    .map(|bubbles| {
+                        bubbles
+                            .results
+                            .iter()
+                            .map(|result| result.id)
+                            .collect::<Vec<_>>()
+                    })
+                    .unwrap_or_default();
+
+                for (position, app_id) in ids.into_iter().enumerate() {
+                    search_results.write(SearchResultRecord {
+                        snapshot,
+                        query: page.term.to_string(),
+                        position,
+                        app_id,
+                    })?;
+                }
+            }
+            Data::Lookup(list) => {
+                for result in list.results {
+                    match result {
+                        LookupResult::Software(software) => {
+                            apps.write(AppRecord::from_software(snapshot, &software))?;
+                            developers.write(DeveloperRecord {
+                                snapshot,
+                                developer_id: software.artist_id.into(),
+                                name: Some(software.artist_name.to_string()),
+                                app_id: Some(software.track_id),
+                            })?;
+                        }
+                        LookupResult::Artist(artist) => {
+                            developers.write(DeveloperRecord {
+                                snapshot,
+                                developer_id: artist.artist_id,
+                                name: Some(artist.artist_name.to_string()),
+                                app_id: None,
+                            })?;
+                        }
+                    }
+                }
+            }
+            Data::Reviews(page) => {
+                for review in page.feed.entries() {
+                    reviews.write(ReviewRecord::new(snapshot, page.id, &review))?;
+                }
+            }
+            // Chart entries only contain summary information, which the export does not use.
+            Data::Chart(_) | Data::Suggestions(_) => {}
+        }
+    }
+
+    Ok(Stats {
+        apps: apps.finish()?,
+        developers: developers.finish()?,
+        reviews: reviews.finish()?,
+        search_results: search_results.finish()?,
+        failed,
+    })
+}
+
+/// An app from either a lookup result or an app page.
+///
+/// App pages do not include a currency code, and only report download sizes per device, so for
+/// rows with a `source` of `app` the currency is empty and the file size is the largest of these.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AppRecord {
+    pub snapshot: Snapshot,
+    pub source: &'static str,
+    pub app_id: u64,
+    pub bundle_id: Option<String>,
+    pub title: String,
+    pub developer_id: u64,
+    pub developer_name: String,
+    pub primary_genre: Option<String>,
+    pub release_date: Option<DateTime<Utc>>,
+    pub version: Option<String>,
+    pub minimum_os_version: String,
+    pub price: Option<f64>,
+    pub currency: Option<String>,
+    pub average_user_rating: Option<f64>,
+    pub user_rating_count: Option<usize>,
+    pub file_size_bytes: Option<usize>,
+    pub has_in_app_purchases: Option<bool>,
+}
+
+impl AppRecord {
+    fn from_software(snapshot: Snapshot, software: &Software<'_>) -> Self {
+        let money = software.money();
+
+        Self {
+            snapshot,
+            source: "lookup",
+            app_id: software.track_id,
+            bundle_id: Some(software.bundle_id.to_string()),
+            title: software.track_name.to_string(),
+            developer_id: software.artist_id.into(),
+            developer_name: software.artist_name.to_string(),
+            primary_genre: Some(software.primary_genre.to_string()),
+            release_date: Some(software.release_date),
+            version: Some(software.version.to_string()),
+            minimum_os_version: software.minimum_os_version.to_string(),
+            price: money.map(|money| money.amount.to_f64()),
+            currency: money.map(|money| money.currency.to_string()),
+            average_user_rating: software.average_user_rating.to_f64(),
+            user_rating_count: Some(software.user_rating_count),
+            file_size_bytes: Some(software.file_size_bytes),
+            has_in_app_purchases: None,
+        }
+    }
+
+    fn from_product_dv(snapshot: Snapshot, app: &ProductDvResult<'_>) -> Self {
+        let offer = app.common.offers.first();
+
+        Self {
+            snapshot,
+            source: "app",
+            app_id: app.id,
+            bundle_id: app.common.bundle_id.as_ref().map(ToString::to_string),
+            title: app.common.name.to_string(),
+            developer_id: app.artist_id,
+            developer_name: app.common.artist_name.to_string(),
+            primary_genre: app.common.genre_names.first().map(ToString::to_string),
+            release_date: app
+                .common
+                .release_date
+                .and_hms_opt(0, 0, 0)
+                .map(|release_date| release_date.and_utc()),
+            version: offer
+                .and_then(|offer| offer.version.as_ref())
+                .map(|version| version.display.to_string()),
+            minimum_os_version: app.common.minimum_os_version.to_string(),
+            price: offer
+                .and_then(crate::model::offer::Offer::amount)
+                .map(app_store_access::money::Amount::to_f64),
+            currency: None,
+            average_user_rating: app.common.user_rating.value.to_f64(),
+            user_rating_count: Some(app.common.user_rating.rating_count as usize),
+            file_size_bytes: app
+                .file_size_by_device
+                .as_ref()
+                .and_then(|sizes| sizes.values().max().copied()),
+            has_in_app_purchases: app.common.has_in_app_purchases,
+        }
+    }
+}
+
+impl Record for AppRecord {
+    fn schema() -> SchemaRef {
+        snapshot_schema([
+            string_field("source", false),
+            u64_field("app_id", false),
+            string_field("bundle_id", true),
+            string_field("title", false),
+            u64_field("developer_id", false),
+            string_field("developer_name", false),
+            string_field("primary_genre", true),
+            timestamp_field("release_date", true),
+            string_field("version", true),
+            string_field("minimum_os_version", false),
+            f64_field("price", true),
+            string_field("currency", true),
+            f64_field("average_user_rating", true),
+            u64_field("user_rating_count", true),
+            u64_field("file_size_bytes", true),
+            bool_field("has_in_app_purchases", true),
+        ])
+    }
+
+    fn columns(records: &[Self]) -> Vec<ArrayRef> {
+        snapshot_columns(
+            records,
+            |record| &record.snapshot,
+            [
+                string_column(records, |record| Some(record.source)),
+                u64_column(records, |record| Some(record.app_id)),
+                string_column(records, |record| record.bundle_id.as_deref()),
+                string_column(records, |record| Some(record.title.as_str())),
+                u64_column(records, |record| Some(record.developer_id)),
+                string_column(records, |record| Some(record.developer_name.as_str())),
+                string_column(records, |record| record.primary_genre.as_deref()),
+                timestamp_column(records, |record| record.release_date),
+                string_column(records, |record| record.version.as_deref()),
+                string_column(records, |record| Some(record.minimum_os_version.as_str())),
+                f64_column(records, |record| record.price),
+                string_column(records, |record| record.currency.as_deref()),
+                f64_column(records, |record| record.average_user_rating),
+                u64_column(records, |record| {
+                    record.user_rating_count.map(|count| count as u64)
+                }),
+                u64_column(records, |record| {
+                    record.file_size_bytes.map(|size| size as u64)
+                }),
+                bool_column(records, |record| record.has_in_app_purchases),
+            ],
+        )
+    }
+}
+
+/// A developer (artist), optionally associated with one of their apps.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DeveloperRecord {
+    pub snapshot: Snapshot,
+    pub developer_id: u64,
+    pub name: Option<String>,
+    pub app_id: Option<u64>,
+}
+
+impl Record for DeveloperRecord {
+    fn schema() -> SchemaRef {
+        snapshot_schema([
+            u64_field("developer_id", false),
+            string_field("name", true),
+            u64_field("app_id", true),
+        ])
+    }
+
+    fn columns(records: &[Self]) -> Vec<ArrayRef> {
+        snapshot_columns(
+            records,
+            |record| &record.snapshot,
+            [
+                u64_column(records, |record| Some(record.developer_id)),
+                string_column(records, |record| record.name.as_deref()),
+                u64_column(records, |record| record.app_id),
+            ],
+        )
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReviewRecord {
+    pub snapshot: Snapshot,
+    pub app_id: u64,
+    pub review_id: u64,
+    pub user_name: String,
+    pub rating: u8,
+    pub timestamp: DateTime<Utc>,
+    pub title: String,
+    pub text: String,
+    pub version: String,
+    pub vote_count: u64,
+    pub vote_sum: u64,
+}
+
+impl ReviewRecord {
+    fn new(snapshot: Snapshot, app_id: u64, entry: &reviews::Entry<'_>) -> Self {
+        Self {
+            snapshot,
+            app_id,
+            review_id: entry.id.label,
+            user_name: entry.author.name.label.to_string(),
+            rating: entry.rating.label,
+            timestamp: entry.updated.label,
+            title: entry.title.label.to_string(),
+            text: entry.content.label.to_string(),
+            version: entry.version.label.to_string(),
+            vote_count: entry.vote_count.label,
+            vote_sum: entry.vote_sum.label,
+        }
+    }
+}
+
+impl Record for ReviewRecord {
+    fn schema() -> SchemaRef {
+        snapshot_schema([
+            u64_field("app_id", false),
+            u64_field("review_id", false),
+            string_field("user_name", false),
+            u64_field("rating", false),
+            timestamp_field("timestamp", false),
+            string_field("title", false),
+            string_field("text", false),
+            string_field("version", false),
+            u64_field("vote_count", false),
+            u64_field("vote_sum", false),
+        ])
+    }
+
+    fn columns(records: &[Self]) -> Vec<ArrayRef> {
+        snapshot_columns(
+            records,
+            |record| &record.snapshot,
+            [
+                u64_column(records, |record| Some(record.app_id)),
+                u64_column(records, |record| Some(record.review_id)),
+                string_column(records, |record| Some(record.user_name.as_str())),
+                u64_column(records, |record| Some(u64::from(record.rating))),
+                timestamp_column(records, |record| Some(record.timestamp)),
+                string_column(records, |record| Some(record.title.as_str())),
+                string_column(records, |record| Some(record.text.as_str())),
+                string_column(records, |record| Some(record.version.as_str())),
+                u64_column(records, |record| Some(record.vote_count)),
+                u64_column(records, |record| Some(record.vote_sum)),
+            ],
+        )
+    }
+}
+
+/// A single search result, with its zero-indexed position in the results.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SearchResultRecord {
+    pub snapshot: Snapshot,
+    pub query: String,
+    pub position: usize,
+    pub app_id: u64,
+}
+
+impl Record for SearchResultRecord {
+    fn schema() -> SchemaRef {
+        snapshot_schema([
+            string_field("query", false),
+            u64_field("position", false),
+            u64_field("app_id", false),
+        ])
+    }
+
+    fn columns(records: &[Self]) -> Vec<ArrayRef> {
+        snapshot_columns(
+            records,
+            |record| &record.snapshot,
+            [
+                string_column(records, |record| Some(record.query.as_str())),
+                u64_column(records, |record| Some(record.position as u64)),
+                u64_column(records, |record| Some(record.app_id)),
+            ],
+        )
+    }
+}
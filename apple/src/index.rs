@@ -0,0 +1,212 @@
+use crate::{
+    archive::Data,
+    model::lookup::{LookupResult, LookupResultList},
+};
+use app_store_access::index::{
+    App, Developer, Index, Review, SearchResult, Snapshot, SnapshotInfo, Stats, Store,
+};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Index error")]
+    Index(#[from] app_store_access::index::Error),
+    #[error("I/O error")]
+    Io(#[from] std::io::Error),
+    #[error("JSON file error")]
+    JsonFile(PathBuf, serde_json::Error),
+}
+
+/// Ingest all archive files that are not already present in the index.
+///
+/// Files that cannot be read or parsed are logged and skipped, and will be retried on the next run.
+pub fn build<P: AsRef<Path>>(archive: P, index: &mut Index) -> Result<Stats, Error> {
+    let archive = archive.as_ref();
+    let mut stats = Stats::default();
+
+    for path in app_store_access::index::archive_paths(archive)? {
+        if index.is_ingested(Store::Apple, &path)? {
+            stats.skipped += 1;
+        } else {
+            match ingest_file(archive, &path, index) {
+                Ok(()) => {
+                    stats.ingested += 1;
+                }
+                Err(Error::Index(error)) => return Err(Error::Index(error)),
+                Err(error) => {
+                    log::warn!("Skipping archive file {}: {error:?}", path.display());
+                    stats.failed += 1;
+                }
+            }
+        }
+    }
+
+    Ok(stats)
+}
+
+fn ingest_file(archive: &Path, path: &Path, index: &mut Index) -> Result<(), Error> {
+    let full_path = archive.join(path);
+    let contents = std::fs::read_to_string(&full_path)?;
+    let entry =
+        serde_json::from_str::<scraper_trail::archive::entry::Entry<Data<'static>>>(&contents)
+            .map_err(|error| Error::JsonFile(full_path.clone(), error))?;
+
+    let request = &entry.request_params;
+    let data = &entry.exchange.response.data;
+
+    let info = SnapshotInfo {
+        kind: kind(data),
+        timestamp: entry.exchange.request.timestamp,
+        country: Some(request.country()),
+        language: request.language(),
+    };
+
+    index.ingest(Store::Apple, path, &info, |snapshot| {
+        add_rows(snapshot, data)
+    })?;
+
+    Ok(())
+}
+
+fn add_rows(
+    snapshot: &Snapshot<'_>,
+    data: &Data<'_>,
+) -> Result<(), app_store_access::index::Error> {
+    match data {
+        Data::App(page) => {
+            for (id, app) in &page.store_platform_data.product_dv.results {
+                let app_id = id.to_string();
+                let artist_id = app.artist_id.to_string();
+
+                snapshot.add_app(&App {
+                    app_id: &app_id,
+                    bundle_id: app.common.bundle_id.as_deref(),
+                    developer_id: Some(&artist_id),
+                    title: &app.common.name,
+                    developer_name: Some(&app.common.artist_name),
+                })?;
+
+                snapshot.add_developer(&Developer {
+                    developer_id: &artist_id,
+                    name: Some(&app.common.artist_name),
+                    app_id: Some(&app_id),
+                })?;
+            }
+        }
+        Data::Search(page) => {
+            let ids = page
+                .bubbles
+                .clone()
+                .bubbles()
+                .map(|bubbles| {
+                    bubbles
+                        .results
+                        .iter()
+                        .map(|result| result.id)
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default();
+
+            for (position, id) in ids.iter().enumerate() {
+                snapshot.add_search_result(&SearchResult {
+                    query: Some(&page.term),
+                    position,
+                    app_id: &id.to_string(),
+                })?;
+            }
+
+            if let Some(results) = &page.store_platform_data.native_search_lockup_search {
+                for (id, app) in &results.results {
+                    let artist_id = app.artist_id.to_string();
+
+                    snapshot.add_app(&App {
+                        app_id: &id.to_string(),
+                        bundle_id: app.common.bundle_id.as_deref(),
+                        developer_id: Some(&artist_id),
+                        title: &app.common.name,
+                        developer_name: Some(&app.common.artist_name),
+                    })?;
+                }
+            }
+        }
+        Data::Lookup(list) => add_lookup_rows(snapshot, list)?,
+        Data::Reviews(page) => {
+            let app_id = page.id.to_string();
+
+            for entry in page.feed.entries() {
+                snapshot.add_review(&Review {
+                    app_id: &app_id,
+                    review_id: &entry.id.label.to_string(),
+                    user_name: &entry.author.name.label,
+                    score: entry.rating.label,
+                    timestamp: Some(entry.updated.label),
+                    text: Some(&entry.content.label),
+                })?;
+            }
+        }
+        Data::Chart(chart) => {
+            for entry in &chart.entries {
+                let artist_id = entry.artist_id.map(|artist_id| artist_id.to_string());
+
+                snapshot.add_app(&App {
+                    app_id: &entry.id.to_string(),
+                    bundle_id: entry.bundle_id.as_deref(),
+                    developer_id: artist_id.as_deref(),
+                    title: &entry.name,
+                    developer_name: entry.artist_name.as_deref(),
+                })?;
+            }
+        }
+        Data::Suggestions(_) => {}
+    }
+
+    Ok(())
+}
+
+fn add_lookup_rows(
+    snapshot: &Snapshot<'_>,
+    list: &LookupResultList<'_>,
+) -> Result<(), app_store_access::index::Error> {
+    for result in &list.results {
+        match result {
+            LookupResult::Software(software) => {
+                let app_id = software.track_id.to_string();
+                let artist_id = software.artist_id.to_string();
+
+                snapshot.add_app(&App {
+                    app_id: &app_id,
+                    bundle_id: Some(&software.bundle_id),
+                    developer_id: Some(&artist_id),
+                    title: &software.track_name,
+                    developer_name: Some(&software.artist_name),
+                })?;
+
+                snapshot.add_developer(&Developer {
+                    developer_id: &artist_id,
+                    name: Some(&software.artist_name),
+                    app_id: Some(&app_id),
+                })?;
+            }
+            LookupResult::Artist(artist) => {
+                snapshot.add_developer(&Developer {
+                    developer_id: &artist.artist_id.to_string(),
+                    name: Some(&artist.artist_name),
+                    app_id: None,
+                })?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+const fn kind(data: &Data<'_>) -> &'static str {
+    match data {
+        Data::App(_) => "app",
+        Data::Search(_) => "search",
+        Data::Lookup(_) => "lookup",
+        Data::Reviews(_) => "reviews",
+        Data::Chart(_) => "chart",
+        Data::Suggestions(_) => "suggestions",
+    }
+}
@@ -0,0 +1,105 @@
+//! Media assets listed on archived Apple product pages.
+use crate::{archive::Data, model::full::ProductDvResult, request::Request};
+use app_store_access::{
+    country::Country,
+    index::Store,
+    language::Language,
+    media::{Asset, Snapshot},
+    model::ImageType,
+};
+use chrono::{DateTime, Utc};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("I/O error")]
+    Io(#[from] std::io::Error),
+    #[error("Archive error")]
+    Store(PathBuf, scraper_trail::archive::store::Error),
+}
+
+/// The icon, screenshots, and preview videos for an app, with artwork templates filled in for
+/// images at most `width` pixels wide.
+#[must_use]
+pub fn assets(app: &ProductDvResult<'_>, width: usize) -> Vec<Asset> {
+    let mut assets = vec![Asset {
+        image_type: ImageType::Icon,
+        device: None,
+        position: 0,
+        url: app.artwork.url_for_width(width, "png"),
+    }];
+
+    for (device, artworks) in app.screenshots_by_type.iter() {
+        assets.extend(
+            artworks
+                .iter()
+                .enumerate()
+                .map(|(position, artwork)| Asset {
+                    image_type: ImageType::Screenshot,
+                    device: Some(device.as_str().to_string()),
+                    position,
+                    url: artwork.url_for_width(width, "jpg"),
+                }),
+        );
+    }
+
+    for (device, video) in app.video_preview_by_type.iter() {
+        assets.push(Asset {
+            image_type: ImageType::Video,
+            device: Some(device.as_str().to_string()),
+            position: 0,
+            url: video.video.to_string(),
+        });
+    }
+
+    assets
+}
+
+/// Build a snapshot from an app listing.
+#[must_use]
+pub fn snapshot(
+    timestamp: DateTime<Utc>,
+    country: Country,
+    language: Option<Language>,
+    app: &ProductDvResult<'_>,
+    width: usize,
+) -> Snapshot {
+    Snapshot {
+        store: Store::Apple,
+        app_id: app.id.to_string(),
+        timestamp,
+        country,
+        language,
+        assets: assets(app, width),
+    }
+}
+
+/// Build snapshots from all archived product pages (optionally only for the given apps).
+pub fn snapshots<P: AsRef<Path>>(
+    archive: P,
+    app_ids: &[u64],
+    width: usize,
+) -> Result<Vec<Snapshot>, Error> {
+    let store = scraper_trail::archive::store::Store::new(archive.as_ref());
+    let mut snapshots = vec![];
+
+    for (path, entry) in store.entries::<Data>(false)? {
+        let entry = entry.map_err(|error| Error::Store(path, error))?;
+
+        if let (Data::App(page), Request::App { id, .. }) =
+            (&entry.exchange.response.data, &entry.request_params)
+            && (app_ids.is_empty() || app_ids.contains(id))
+            && let Some(app) = page.store_platform_data.product_dv.results.get(id)
+        {
+            snapshots.push(snapshot(
+                entry.exchange.request.timestamp,
+                entry.request_params.country(),
+                entry.request_params.language(),
+                app,
+                width,
+            ));
+        }
+    }
+
+    Ok(snapshots)
+}
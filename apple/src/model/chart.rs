@@ -0,0 +1,190 @@
+//! Top charts from the App Store RSS feeds.
+//!
+//! The feeds include many presentation fields (images, links, rights), so only the fields that
+//! identify each app are kept, and entries are in chart order.
+use super::genre::Genre;
+
+static ARTIST_URL_RE: std::sync::LazyLock<regex::Regex> =
+    std::sync::LazyLock::new(|| regex::Regex::new(r"/id(\d+)(?:\?|$)").unwrap());
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, serde::Serialize)]
+pub struct Chart {
+    pub entries: Vec<Entry>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+pub struct Entry {
+    pub id: u64,
+    pub bundle_id: Option<String>,
+    pub name: String,
+    pub artist_id: Option<u64>,
+    pub artist_name: Option<String>,
+    pub genre: Option<Genre>,
+    /// The price as a decimal string (for example `0.00000`).
+    pub price: Option<String>,
+    pub currency: Option<String>,
+}
+
+impl<'de> serde::de::Deserialize<'de> for Chart {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let internal = InternalChart::deserialize(deserializer)?;
+
+        let entries = match internal.feed.entry {
+            None => vec![],
+            Some(InternalEntryList::Singleton(entry)) => vec![*entry],
+            Some(InternalEntryList::Multi(entries)) => entries,
+        };
+
+        Ok(Self {
+            entries: entries
+                .into_iter()
+                .map(|entry| {
+                    let id = entry.id.attributes.id.parse().map_err(|_| {
+                        serde::de::Error::invalid_value(
+                            serde::de::Unexpected::Str(&entry.id.attributes.id),
+                            &"an app ID",
+                        )
+                    })?;
+
+                    let (price, currency) = entry
+                        .price
+                        .map(|price| (price.attributes.amount, price.attributes.currency))
+                        .unzip();
+
+                    Ok(Entry {
+                        id,
+                        bundle_id: entry.id.attributes.bundle_id,
+                        name: entry.name.label,
+                        artist_id: entry
+                            .artist
+                            .as_ref()
+                            .and_then(|artist| artist.attributes.as_ref())
+                            .and_then(|attributes| ARTIST_URL_RE.captures(&attributes.href))
+                            .and_then(|captures| captures.get(1))
+                            .and_then(|id| id.as_str().parse().ok()),
+                        artist_name: entry.artist.map(|artist| artist.label),
+                        genre: entry
+                            .category
+                            .and_then(|category| category.attributes.id.parse().ok())
+                            .and_then(Genre::from_id),
+                        price,
+                        currency,
+                    })
+                })
+                .collect::<Result<Vec<_>, D::Error>>()?,
+        })
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct InternalChart {
+    feed: InternalFeed,
+}
+
+#[derive(serde::Deserialize)]
+struct InternalFeed {
+    entry: Option<InternalEntryList>,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum InternalEntryList {
+    Singleton(Box<InternalEntry>),
+    Multi(Vec<InternalEntry>),
+}
+
+#[derive(serde::Deserialize)]
+struct InternalEntry {
+    id: InternalId,
+    #[serde(rename = "im:name")]
+    name: InternalLabel,
+    #[serde(rename = "im:artist")]
+    artist: Option<InternalArtist>,
+    category: Option<InternalCategory>,
+    #[serde(rename = "im:price")]
+    price: Option<InternalPrice>,
+}
+
+#[derive(serde::Deserialize)]
+struct InternalLabel {
+    label: String,
+}
+
+#[derive(serde::Deserialize)]
+struct InternalId {
+    attributes: InternalIdAttributes,
+}
+
+#[derive(serde::Deserialize)]
+struct InternalIdAttributes {
+    #[serde(rename = "im:id")]
+    id: String,
+    #[serde(rename = "im:bundleId")]
+    bundle_id: Option<String>,
+}
+
+#[derive(serde::Deserialize)]
+struct InternalArtist {
+    label: String,
+    attributes: Option<InternalHref>,
+}
+
+#[derive(serde::Deserialize)]
+struct InternalHref {
+    href: String,
+}
+
+#[derive(serde::Deserialize)]
+struct InternalCategory {
+    attributes: InternalCategoryAttributes,
+}
+
+#[derive(serde::Deserialize)]
+struct InternalCategoryAttributes {
+    #[serde(rename = "im:id")]
+    id: String,
+}
+
+#[derive(serde::Deserialize)]
+struct InternalPrice {
+    attributes: InternalPriceAttributes,
+}
+
+#[derive(serde::Deserialize)]
+struct InternalPriceAttributes {
+    amount: String,
+    currency: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_chart() {
+        let json = r#"{"feed": {"entry": [{
+            "im:name": {"label": "Puzzle Game"},
+            "id": {
+                "label": "https://apps.apple.com/us/app/puzzle-game/id123?uo=2",
+                "attributes": {"im:id": "123", "im:bundleId": "com.example.puzzle"}
+            },
+            "im:artist": {
+                "label": "Example",
+                "attributes": {"href": "https://apps.apple.com/us/developer/example/id456?uo=2"}
+            },
+            "category": {"attributes": {"im:id": "7012", "term": "Puzzle", "label": "Puzzle"}},
+            "im:price": {"label": "Get", "attributes": {"amount": "0.00000", "currency": "USD"}}
+        }]}}"#;
+
+        let chart = serde_json::from_str::<Chart>(json).unwrap();
+
+        assert_eq!(chart.entries.len(), 1);
+        assert_eq!(chart.entries[0].id, 123);
+        assert_eq!(chart.entries[0].artist_id, Some(456));
+        assert_eq!(chart.entries[0].genre, Some(Genre::GamesPuzzle));
+
+        let empty = serde_json::from_str::<Chart>(r#"{"feed": {"author": {}}}"#).unwrap();
+
+        assert!(empty.entries.is_empty());
+    }
+}
@@ -2,7 +2,7 @@ use bounded_static_derive_more::ToStatic;
 use chrono::{DateTime, Utc};
 use serde_field_attributes::{integer_str, integer_str_array, optional_integer_str_array};
 use std::borrow::Cow;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 #[derive(Clone, Debug, Eq, PartialEq, ToStatic, serde::Deserialize, serde::Serialize)]
 #[serde(deny_unknown_fields)]
@@ -14,6 +14,145 @@ pub struct Page<'a> {
     pub properties: Properties<'a>,
 }
 
+impl Page<'_> {
+    #[must_use]
+    pub const fn id(&self) -> u64 {
+        self.page_data.id()
+    }
+
+    /// When the page was generated.
+    #[must_use]
+    pub const fn timestamp(&self) -> DateTime<Utc> {
+        self.properties.timestamp
+    }
+
+    /// Apps listed on the page, in page order (an app may appear with more than one relation).
+    #[must_use]
+    pub fn related_apps(&self) -> Vec<RelatedApp> {
+        let id = self.id();
+        let mut related = vec![];
+
+        if let PageData::SoftwarePage(software_page) = &self.page_data {
+            let lists = [
+                (
+                    Relation::AlsoLike,
+                    software_page.customers_also_bought_apps.as_deref(),
+                ),
+                (
+                    Relation::MoreByDeveloper,
+                    software_page.more_by_this_developer.as_deref(),
+                ),
+                (
+                    Relation::TopApp,
+                    software_page
+                        .top_apps
+                        .iphone
+                        .as_ref()
+                        .map(|list| list.ids.as_slice()),
+                ),
+            ];
+
+            for (relation, ids) in lists {
+                related.extend(
+                    ids.unwrap_or_default()
+                        .iter()
+                        .map(|id| RelatedApp::new(*id, relation)),
+                );
+            }
+        }
+
+        if let Some(result) = self.store_platform_data.product_dv.results.get(&id) {
+            let child_ids = result.children_ids.clone().unwrap_or_else(|| {
+                result
+                    .children
+                    .as_ref()
+                    .map(|children| children.keys().copied().collect())
+                    .unwrap_or_default()
+            });
+
+            related.extend(
+                child_ids
+                    .into_iter()
+                    .map(|id| RelatedApp::new(id, Relation::BundleChild)),
+            );
+        }
+
+        related.retain(|related_app| related_app.id != id);
+        related
+    }
+
+    /// The IDs of all related apps, in page order and without duplicates.
+    #[must_use]
+    pub fn related_app_ids(&self) -> Vec<u64> {
+        let mut seen = std::collections::HashSet::new();
+
+        self.related_apps()
+            .into_iter()
+            .map(|related_app| related_app.id)
+            .filter(|id| seen.insert(*id))
+            .collect()
+    }
+}
+
+/// How an app listed on a product page is related to the page's app.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, serde::Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Relation {
+    /// "You might also like".
+    AlsoLike,
+    /// "More by this developer".
+    MoreByDeveloper,
+    /// An app included in the page's app bundle.
+    BundleChild,
+    /// An app from the top chart shown on the page.
+    TopApp,
+}
+
+impl Relation {
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::AlsoLike => "also-like",
+            Self::MoreByDeveloper => "more-by-developer",
+            Self::BundleChild => "bundle-child",
+            Self::TopApp => "top-app",
+        }
+    }
+}
+
+impl std::fmt::Display for Relation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for Relation {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "also-like" => Ok(Self::AlsoLike),
+            "more-by-developer" => Ok(Self::MoreByDeveloper),
+            "bundle-child" => Ok(Self::BundleChild),
+            "top-app" => Ok(Self::TopApp),
+            other => Err(other.to_string()),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, serde::Serialize)]
+pub struct RelatedApp {
+    pub id: u64,
+    pub relation: Relation,
+}
+
+impl RelatedApp {
+    #[must_use]
+    pub const fn new(id: u64, relation: Relation) -> Self {
+        Self { id, relation }
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, ToStatic, serde::Deserialize, serde::Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct StorePlatformData<'a> {
@@ -69,12 +208,25 @@ pub struct ProductDvResult<'a> {
     pub uber: Option<Uber<'a>>,
     #[serde(rename = "appCount")]
     pub app_count: Option<usize>,
-    // TODO: Model this.
-    pub children: Option<serde_json::Value>,
+    pub children: Option<BTreeMap<u64, BundleChild<'a>>>,
     #[serde(rename = "childrenIds", with = "optional_integer_str_array", default)]
     pub children_ids: Option<Vec<u64>>,
 }
 
+/// A listing for an app included in a bundle.
+///
+/// These are abbreviated product results, so unknown fields are ignored here.
+#[derive(Clone, Debug, Eq, PartialEq, ToStatic, serde::Deserialize, serde::Serialize)]
+pub struct BundleChild<'a> {
+    #[serde(with = "integer_str")]
+    pub id: u64,
+    pub name: Cow<'a, str>,
+    #[serde(rename = "artistName")]
+    pub artist_name: Option<Cow<'a, str>>,
+    pub url: Option<Cow<'a, str>>,
+    pub artwork: Option<super::Artwork<'a>>,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, ToStatic, serde::Deserialize, serde::Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct Video<'a> {
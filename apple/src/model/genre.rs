@@ -165,6 +165,72 @@ impl Genre {
             .iter()
             .map(|(genre, code, names)| (*genre, *code, names[0]))
     }
+
+    /// The genre that contains this genre.
+    ///
+    /// Top-level genres belong to [`Genre::All`], which is the only genre without a parent.
+    #[must_use]
+    pub const fn parent(&self) -> Option<Self> {
+        match self {
+            Self::All => None,
+            Self::GamesAction
+            | Self::GamesAdventure
+            | Self::GamesCasual
+            | Self::GamesBoard
+            | Self::GamesCard
+            | Self::GamesCasino
+            | Self::GamesDice
+            | Self::GamesFamily
+            | Self::GamesMusic
+            | Self::GamesPuzzle
+            | Self::GamesRacing
+            | Self::GamesRolePlaying
+            | Self::GamesSimulation
+            | Self::GamesSports
+            | Self::GamesStrategy
+            | Self::GamesTrivia
+            | Self::GamesWord => Some(Self::Games),
+            Self::StickersEmojiAndExpressions
+            | Self::StickersAnimalsAndNature
+            | Self::StickersArt
+            | Self::StickersCelebrations
+            | Self::StickersCelebrities
+            | Self::StickersComicsAndCartoons
+            | Self::StickersEatingAndDrinking
+            | Self::StickersGaming
+            | Self::StickersMoviesAndTv
+            | Self::StickersMusic
+            | Self::StickersPeople
+            | Self::StickersPlacesAndObjects
+            | Self::StickersSportsAndActivities
+            | Self::StickersKidsAndFamily
+            | Self::StickersFashion => Some(Self::Stickers),
+            _ => Some(Self::All),
+        }
+    }
+
+    /// The genres directly contained in this genre (in ID order).
+    pub fn children(&self) -> impl Iterator<Item = Self> {
+        let genre = *self;
+
+        GENRE_INFO
+            .iter()
+            .map(|(child, _, _)| *child)
+            .filter(move |child| child.parent() == Some(genre))
+    }
+
+    /// The top-level genre containing this genre (for example [`Genre::Games`] for
+    /// [`Genre::GamesPuzzle`]).
+    ///
+    /// Returns `None` for [`Genre::All`].
+    #[must_use]
+    pub const fn top_level(&self) -> Option<Self> {
+        match self.parent() {
+            None => None,
+            Some(Self::All) => Some(*self),
+            Some(parent) => Some(parent),
+        }
+    }
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
@@ -701,3 +767,31 @@ pub mod from_id_strs {
         ids.serialize(serializer)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn genre_tree() {
+        assert_eq!(Genre::GamesPuzzle.parent(), Some(Genre::Games));
+        assert_eq!(Genre::Games.parent(), Some(Genre::All));
+        assert_eq!(Genre::All.parent(), None);
+        assert_eq!(Genre::GamesPuzzle.top_level(), Some(Genre::Games));
+        assert_eq!(Genre::Weather.top_level(), Some(Genre::Weather));
+
+        assert_eq!(Genre::Games.children().count(), 17);
+        assert_eq!(Genre::Stickers.children().count(), 15);
+        assert_eq!(Genre::Weather.children().count(), 0);
+
+        // Every genre is reachable from the root.
+        let top_level = Genre::All.children().collect::<Vec<_>>();
+        let reachable = top_level.len()
+            + top_level
+                .iter()
+                .map(|genre| genre.children().count())
+                .sum::<usize>();
+
+        assert_eq!(reachable + 1, Genre::values().count());
+    }
+}
@@ -14,6 +14,39 @@ pub struct LookupResultList<'a> {
     pub metrics_base: Option<super::metrics::MetricsBase<'a>>,
 }
 
+impl<'a> LookupResultList<'a> {
+    /// Split the results of a developer lookup into the developer and their apps.
+    ///
+    /// Returns `None` if the results do not include the developer (for example if the ID is not a
+    /// developer ID).
+    #[must_use]
+    pub fn into_developer(self, artist_id: u64) -> Option<Developer<'a>> {
+        let mut artist = None;
+        let mut apps = vec![];
+
+        for result in self.results {
+            match result {
+                LookupResult::Artist(value) if value.artist_id == artist_id => {
+                    artist = Some(value);
+                }
+                LookupResult::Artist(_) => {}
+                LookupResult::Software(software) => {
+                    apps.push(*software);
+                }
+            }
+        }
+
+        artist.map(|artist| Developer { artist, apps })
+    }
+}
+
+/// A developer (artist) with all of their apps.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+pub struct Developer<'a> {
+    pub artist: Artist<'a>,
+    pub apps: Vec<Software<'a>>,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, ToStatic, serde::Deserialize, serde::Serialize)]
 #[serde(deny_unknown_fields, tag = "wrapperType")]
 pub enum LookupResult<'a> {
@@ -133,3 +166,16 @@ pub struct Software<'a> {
     #[serde(rename = "averageUserRatingForCurrentVersion", with = "ratio_u64")]
     pub average_user_rating_for_current_version: num_rational::Ratio<u64>,
 }
+
+impl Software<'_> {
+    /// The price in the store's currency (not available for some apps).
+    #[must_use]
+    pub fn money(&self) -> Option<app_store_access::money::Money> {
+        self.price
+            .and_then(|price| {
+                app_store_access::money::Amount::from_ratio(*price.numer(), *price.denom())
+            })
+            .zip(self.currency.try_into().ok())
+            .map(|(amount, currency)| app_store_access::money::Money::new(amount, currency))
+    }
+}
@@ -1,3 +1,10 @@
+//! Models for App Store responses.
+//!
+//! All public model types implement both `Deserialize` and `Serialize`. Types that are parsed
+//! directly from Apple's JSON serialize back to Apple's field names (for example `trackId`), so
+//! serialized values can be read by the same deserializers. Types that are derived from the
+//! response (such as [`reviews::Page`]) use the Rust field names.
+
 use app_store_access::{
     country::{Country, country_code_uppercase},
     language::{RegionalLanguage, regional_language_code_lowercase},
@@ -8,6 +15,7 @@ use serde_field_attributes::{integer_str, optional_ratio_u64, ratio_u64};
 use std::borrow::Cow;
 use std::collections::BTreeMap;
 
+pub mod chart;
 pub mod content_rating;
 pub mod devices;
 pub mod full;
@@ -74,6 +82,67 @@ pub enum Currency {
     Zar,
 }
 
+impl Currency {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Aed => "AED",
+            Self::Aud => "AUD",
+            Self::Bgn => "BGN",
+            Self::Brl => "BRL",
+            Self::Cad => "CAD",
+            Self::Chf => "CHF",
+            Self::Clp => "CLP",
+            Self::Cny => "CNY",
+            Self::Cop => "COP",
+            Self::Czk => "CZK",
+            Self::Dkk => "DKK",
+            Self::Egp => "EGP",
+            Self::Eur => "EUR",
+            Self::Gbp => "GBP",
+            Self::Hkd => "HKD",
+            Self::Huf => "HUF",
+            Self::Idr => "IDR",
+            Self::Ils => "ILS",
+            Self::Inr => "INR",
+            Self::Jpy => "JPY",
+            Self::Krw => "KRW",
+            Self::Kzt => "KZT",
+            Self::Mxn => "MXN",
+            Self::Myr => "MYR",
+            Self::Ngn => "NGN",
+            Self::Nok => "NOK",
+            Self::Nzd => "NZD",
+            Self::Pen => "PEN",
+            Self::Pkr => "PKR",
+            Self::Php => "PHP",
+            Self::Pln => "PLN",
+            Self::Qar => "QAR",
+            Self::Ron => "RON",
+            Self::Rub => "RUB",
+            Self::Sar => "SAR",
+            Self::Sek => "SEK",
+            Self::Sgd => "SGD",
+            Self::Thb => "THB",
+            Self::Try => "TRY",
+            Self::Twd => "TWD",
+            Self::Tzs => "TZS",
+            Self::Usd => "USD",
+            Self::Vnd => "VND",
+            Self::Zar => "ZAR",
+        }
+    }
+}
+
+impl TryFrom<Currency> for app_store_access::money::CurrencyCode {
+    type Error = app_store_access::money::Error;
+
+    /// Every variant is a valid currency code, so this never fails in practice.
+    fn try_from(value: Currency) -> Result<Self, Self::Error> {
+        value.as_str().parse()
+    }
+}
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
 #[serde(deny_unknown_fields, rename_all = "lowercase")]
 pub enum EntityType {
@@ -149,7 +218,9 @@ pub struct Notes<'a> {
     pub tagline: Option<Cow<'a, str>>,
 }
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+#[derive(
+    Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, serde::Deserialize, serde::Serialize,
+)]
 #[serde(deny_unknown_fields)]
 pub enum DeviceFamily {
     #[serde(rename = "ipad")]
@@ -168,6 +239,44 @@ pub enum DeviceFamily {
     RealityDevice,
 }
 
+impl DeviceFamily {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Ipad => "ipad",
+            Self::Iphone => "iphone",
+            Self::Ipod => "ipod",
+            Self::Mac => "mac",
+            Self::Tvos => "tvos",
+            Self::Watch => "watch",
+            Self::RealityDevice => "realityDevice",
+        }
+    }
+
+    /// The family for a device identifier, either a model name from the lookup API (for example
+    /// `iPhone15-iPhone15`) or a hardware identifier from a product page (for example `iPhone15,2`).
+    #[must_use]
+    pub fn from_identifier(identifier: &str) -> Option<Self> {
+        if identifier.starts_with("iPhone") {
+            Some(Self::Iphone)
+        } else if identifier.starts_with("iPad") {
+            Some(Self::Ipad)
+        } else if identifier.starts_with("iPod") {
+            Some(Self::Ipod)
+        } else if identifier.starts_with("Watch") {
+            Some(Self::Watch)
+        } else if identifier.starts_with("AppleTV") {
+            Some(Self::Tvos)
+        } else if identifier.starts_with("AppleVision") || identifier.starts_with("RealityDevice") {
+            Some(Self::RealityDevice)
+        } else if identifier.starts_with("Mac") {
+            Some(Self::Mac)
+        } else {
+            None
+        }
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, ToStatic, serde::Deserialize, serde::Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct Artwork<'a> {
@@ -193,6 +302,25 @@ pub struct Artwork<'a> {
     pub gradient: Option<Gradient>,
 }
 
+impl Artwork<'_> {
+    /// Fill in the templated URL (`{w}x{h}{c}.{f}`) for an image at most `width` pixels wide.
+    ///
+    /// The aspect ratio is preserved, and the image is never scaled past its original size.
+    #[must_use]
+    pub fn url_for_width(&self, width: usize, format: &str) -> String {
+        let width = width.min(self.width).max(1);
+        let height = (self.height * width)
+            .checked_div(self.width)
+            .map_or(self.height, |height| height.max(1));
+
+        self.url
+            .replace("{w}", &width.to_string())
+            .replace("{h}", &height.to_string())
+            .replace("{c}", "bb")
+            .replace("{f}", format)
+    }
+}
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct Gradient {}
@@ -570,6 +698,25 @@ pub mod offer {
         pub expected_release_date: Option<NaiveDate>,
     }
 
+    impl Offer<'_> {
+        /// The price in the storefront's currency.
+        #[must_use]
+        pub fn amount(&self) -> Option<app_store_access::money::Amount> {
+            app_store_access::money::Amount::from_ratio(*self.price.numer(), *self.price.denom())
+        }
+
+        /// Product pages do not include a currency code, so the storefront's currency must be
+        /// provided (for example from a lookup result for the same storefront).
+        #[must_use]
+        pub fn money(
+            &self,
+            currency: app_store_access::money::CurrencyCode,
+        ) -> Option<app_store_access::money::Money> {
+            self.amount()
+                .map(|amount| app_store_access::money::Money::new(amount, currency))
+        }
+    }
+
     #[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
     #[serde(deny_unknown_fields)]
     pub struct ActionText {
@@ -18,10 +18,11 @@ pub enum Error {
     InvalidLastLink(String),
 }
 
-#[derive(Clone, Debug, Eq, PartialEq, ToStatic)]
+#[derive(Clone, Debug, Eq, PartialEq, ToStatic, serde::Serialize)]
 pub struct Page<'a> {
     pub feed: Feed<'a>,
     pub id: u64,
+    #[serde(with = "app_store_access::country::country_code")]
     pub country: app_store_access::country::Country,
     pub current_page: usize,
     pub last_page: Option<usize>,
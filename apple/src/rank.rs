@@ -0,0 +1,63 @@
+//! Keyword rank runs from Apple search pages.
+use crate::{
+    archive::Data,
+    model::search::{BubblesField, Page},
+    request::Request,
+};
+use app_store_access::{country::Country, index::Store, rank::Run};
+use chrono::{DateTime, Utc};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("I/O error")]
+    Io(#[from] std::io::Error),
+    #[error("Archive error")]
+    Store(PathBuf, scraper_trail::archive::store::Error),
+}
+
+/// Build a run from a search page, using the app order of the page's results bubble.
+#[must_use]
+pub fn run(timestamp: DateTime<Utc>, country: Country, keyword: &str, page: &Page<'_>) -> Run {
+    let app_ids = match &page.bubbles {
+        BubblesField::Bubbles((bubbles,)) => bubbles
+            .results
+            .iter()
+            .map(|result| result.id.to_string())
+            .collect(),
+        BubblesField::Empty(_) => vec![],
+    };
+
+    Run {
+        timestamp,
+        store: Store::Apple,
+        country,
+        keyword: keyword.to_string(),
+        app_ids,
+    }
+}
+
+/// Rebuild runs from all archived search pages.
+///
+/// The keyword is taken from the page, since the archived request only includes the encoded query.
+pub fn runs<P: AsRef<Path>>(archive: P) -> Result<Vec<Run>, Error> {
+    let store = scraper_trail::archive::store::Store::new(archive.as_ref());
+    let mut runs = vec![];
+
+    for (path, entry) in store.entries::<Data>(false)? {
+        let entry = entry.map_err(|error| Error::Store(path, error))?;
+
+        if let (Data::Search(page), Request::Search { country, .. }) =
+            (&entry.exchange.response.data, &entry.request_params)
+        {
+            runs.push(run(
+                entry.exchange.request.timestamp,
+                *country,
+                &page.term,
+                page,
+            ));
+        }
+    }
+
+    Ok(runs)
+}
@@ -6,17 +6,25 @@ use std::str::FromStr;
 pub(super) const APPLE_STORE_FRONT_HEADER_NAME: &str = "X-Apple-Store-Front";
 pub(super) const ACCEPT_LANGUAGE_HEADER_NAME: &str = "Accept-Language";
 
+/// The store front header value, in the form `{store_id}[-{language_id}],{code}[ {attribute}]`.
 pub struct AppleStoreFrontHeader {
     pub store_id: u32,
+    pub language_id: Option<u16>,
     pub code: u8,
     pub attribute: Option<String>,
 }
 
 impl AppleStoreFrontHeader {
     #[must_use]
-    pub const fn new(store_id: u32, code: u8, attribute: Option<String>) -> Self {
+    pub const fn new(
+        store_id: u32,
+        language_id: Option<u16>,
+        code: u8,
+        attribute: Option<String>,
+    ) -> Self {
         Self {
             store_id,
+            language_id,
             code,
             attribute,
         }
@@ -35,7 +43,18 @@ impl FromStr for AppleStoreFrontHeader {
             let parts = with_attribute[0].split(',').collect::<Vec<_>>();
 
             if parts.len() == 2 {
-                let (store_id, code) = parts[0]
+                let (store_id_str, language_id_str) = match parts[0].split_once('-') {
+                    Some((store_id_str, language_id_str)) => (store_id_str, Some(language_id_str)),
+                    None => (parts[0], None),
+                };
+
+                let language_id = language_id_str
+                    .map(|language_id_str| {
+                        language_id_str.parse::<u16>().map_err(|_| s.to_string())
+                    })
+                    .transpose()?;
+
+                let (store_id, code) = store_id_str
                     .parse::<u32>()
                     .ok()
                     .zip(parts[1].parse::<u8>().ok())
@@ -43,6 +62,7 @@ impl FromStr for AppleStoreFrontHeader {
 
                 Ok(Self {
                     store_id,
+                    language_id,
                     code,
                     attribute: with_attribute
                         .get(1)
@@ -57,7 +77,13 @@ impl FromStr for AppleStoreFrontHeader {
 
 impl Display for AppleStoreFrontHeader {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{},{}", self.store_id, self.code)?;
+        write!(f, "{}", self.store_id)?;
+
+        if let Some(language_id) = self.language_id {
+            write!(f, "-{language_id}")?;
+        }
+
+        write!(f, ",{}", self.code)?;
 
         self.attribute
             .as_ref()
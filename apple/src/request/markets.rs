@@ -2,7 +2,7 @@ use std::collections::{BTreeMap, HashMap};
 use std::fmt::Display;
 use std::sync::LazyLock;
 
-use app_store_access::country::Country;
+use app_store_access::{country::Country, language::Language};
 
 pub struct MarketCode(pub u32);
 
@@ -11,7 +11,7 @@ impl MarketCode {
     pub fn known_countries() -> Vec<Country> {
         let mut countries = MARKETS_VALUES
             .iter()
-            .map(|(_, code)| Self(*code).into())
+            .filter_map(|(_, code)| Self(*code).try_into().ok())
             .collect::<Vec<Country>>();
 
         countries.sort();
@@ -19,27 +19,35 @@ impl MarketCode {
     }
 }
 
-impl Default for MarketCode {
-    fn default() -> Self {
-        Self(143441)
-    }
+#[derive(Clone, Copy, Debug, Eq, PartialEq, thiserror::Error)]
+pub enum Error {
+    #[error("No App Store market for country: {0}")]
+    UnsupportedCountry(Country),
+    #[error("Unknown App Store market code: {0}")]
+    UnknownMarketCode(u32),
+    #[error("Unknown App Store language code: {0}")]
+    UnknownLanguageCode(u16),
 }
 
-impl From<Country> for MarketCode {
-    fn from(value: Country) -> Self {
+impl TryFrom<Country> for MarketCode {
+    type Error = Error;
+
+    fn try_from(value: Country) -> Result<Self, Self::Error> {
         MARKETS
             .get(&value.as_str().to_uppercase())
             .map(|market_code| Self(*market_code))
-            .unwrap_or_default()
+            .ok_or(Error::UnsupportedCountry(value))
     }
 }
 
-impl From<MarketCode> for Country {
-    fn from(value: MarketCode) -> Self {
+impl TryFrom<MarketCode> for Country {
+    type Error = Error;
+
+    fn try_from(value: MarketCode) -> Result<Self, Self::Error> {
         COUNTRIES
             .get(&value.0)
             .and_then(|country_code| country_code.to_lowercase().parse().ok())
-            .unwrap()
+            .ok_or(Error::UnknownMarketCode(value.0))
     }
 }
 
@@ -49,6 +57,77 @@ impl Display for MarketCode {
     }
 }
 
+/// A store front language identifier (the number after the dash in `143455-5,29`).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct LanguageCode(pub u16);
+
+impl LanguageCode {
+    /// Find the language code for a language in a given market.
+    ///
+    /// An exact regional match is preferred (for example French in Canada), with the first listed
+    /// region for the language as a fallback. Returns `None` if the language has no known code, in
+    /// which case the store front's default language will be used.
+    #[must_use]
+    pub fn for_storefront(language: Language, country: Country) -> Option<Self> {
+        LANGUAGES_VALUES
+            .iter()
+            .find(|(value_language, value_country, _)| {
+                *value_language == language && *value_country == country
+            })
+            .or_else(|| {
+                LANGUAGES_VALUES
+                    .iter()
+                    .find(|(value_language, _, _)| *value_language == language)
+            })
+            .map(|(_, _, code)| Self(*code))
+    }
+}
+
+impl TryFrom<LanguageCode> for Language {
+    type Error = Error;
+
+    fn try_from(value: LanguageCode) -> Result<Self, Self::Error> {
+        LANGUAGES_VALUES
+            .iter()
+            .find(|(_, _, code)| *code == value.0)
+            .map(|(language, _, _)| *language)
+            .ok_or(Error::UnknownLanguageCode(value.0))
+    }
+}
+
+impl Display for LanguageCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Known store front language codes, with the default region for each language listed first.
+pub const LANGUAGES_VALUES: [(Language, Country, u16); 23] = [
+    (Language::English, Country::UnitedStates, 1),
+    (Language::English, Country::GreatBritain, 2),
+    (Language::French, Country::France, 3),
+    (Language::German, Country::Germany, 4),
+    (Language::French, Country::Canada, 5),
+    (Language::English, Country::Canada, 6),
+    (Language::Italian, Country::Italy, 7),
+    (Language::Spanish, Country::Spain, 8),
+    (Language::Japanese, Country::Japan, 9),
+    (Language::Dutch, Country::Netherlands, 10),
+    (Language::Danish, Country::Denmark, 11),
+    (Language::Finnish, Country::Finland, 12),
+    (Language::Korean, Country::SouthKorea, 13),
+    (Language::NorwegianBokmal, Country::Norway, 14),
+    (Language::Portuguese, Country::Brazil, 15),
+    (Language::Russian, Country::Russia, 16),
+    (Language::Swedish, Country::Sweden, 17),
+    (Language::Chinese, Country::China, 19),
+    (Language::Chinese, Country::Taiwan, 18),
+    (Language::Portuguese, Country::Portugal, 24),
+    (Language::Turkish, Country::Turkey, 25),
+    (Language::English, Country::Australia, 27),
+    (Language::Spanish, Country::Mexico, 28),
+];
+
 static MARKETS: LazyLock<HashMap<String, u32>> = LazyLock::new(|| {
     MARKETS_VALUES
         .iter()
@@ -178,14 +257,49 @@ pub const MARKETS_VALUES: [(&str, u32); 110] = [
 
 #[cfg(test)]
 mod tests {
-    use crate::request::markets::{MARKETS_VALUES, MarketCode};
-    use app_store_access::country::Country;
+    use crate::request::markets::{LanguageCode, MARKETS_VALUES, MarketCode};
+    use app_store_access::{country::Country, language::Language};
 
     #[test]
     fn country_from_market_code() {
         for (_, code) in MARKETS_VALUES {
-            let code = MarketCode(code);
-            let _country: Country = code.into();
+            let country = Country::try_from(MarketCode(code)).unwrap();
+
+            assert!(country.is_iso());
+            assert_eq!(MarketCode::try_from(country).unwrap().0, code);
         }
     }
+
+    #[test]
+    fn storefront_language_code() {
+        assert_eq!(
+            MarketCode::try_from(Country::Canada).map(|code| code.0),
+            Ok(143455)
+        );
+        assert_eq!(
+            LanguageCode::for_storefront(Language::French, Country::Canada),
+            Some(LanguageCode(5))
+        );
+        assert_eq!(
+            LanguageCode::for_storefront(Language::French, Country::Belgium),
+            Some(LanguageCode(3))
+        );
+        assert_eq!(
+            LanguageCode::for_storefront(Language::Welsh, Country::GreatBritain),
+            None
+        );
+        assert_eq!(Language::try_from(LanguageCode(5)), Ok(Language::French));
+    }
+
+    #[test]
+    fn unsupported_country() {
+        assert_eq!(
+            MarketCode::try_from(Country::NorthKorea).map(|code| code.0),
+            Err(super::Error::UnsupportedCountry(Country::NorthKorea))
+        );
+        assert_eq!(
+            Country::try_from(MarketCode(1)),
+            Err(super::Error::UnknownMarketCode(1))
+        );
+    }
 }
@@ -1,3 +1,4 @@
+use crate::model::genre::Genre;
 use app_store_access::{country::Country, language::Language};
 use chrono::{DateTime, Utc};
 use regex::Regex;
@@ -13,11 +14,21 @@ pub mod params;
 const APP_URL: &str = "https://itunes.apple.com/us/app/app/id";
 const SEARCH_URL: &str = "https://search.itunes.apple.com/WebObjects/MZStore.woa/wa/search?clientApplication=Software&media=software&term=";
 const LOOKUP_URL: &str = "https://itunes.apple.com/lookup";
+/// The maximum number of related entities returned by a lookup.
+const LOOKUP_LIMIT: usize = 200;
+/// The maximum number of entries in an RSS top chart.
+const CHART_LIMIT: usize = 200;
+const SUGGEST_URL: &str = "https://search.itunes.apple.com/WebObjects/MZSearchHints.woa/wa/hints?clientApplication=Software&term=";
 
 static REVIEWS_URL_RE: LazyLock<Regex> = LazyLock::new(|| {
     Regex::new(r"^https://itunes.apple.com/(\w{2})/rss/customerreviews/page=(\d+)/id=(\d+)/sortby=([^/]+)/json$").unwrap()
 });
 
+static CHART_URL_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^https://itunes.apple.com/(\w{2})/rss/(\w+)/limit=\d+(?:/genre=(\d+))?/json$")
+        .unwrap()
+});
+
 static RATINGS_URL_RE: LazyLock<Regex> = LazyLock::new(|| {
     Regex::new(r"^https://itunes.apple.com/(\w{2})/customer-reviews/id(\d+)\?displayable-kind=11$")
         .unwrap()
@@ -28,6 +39,7 @@ pub enum Request<'a> {
     App {
         id: u64,
         country: Country,
+        language: Option<Language>,
     },
     Search {
         query: Cow<'a, str>,
@@ -44,6 +56,12 @@ pub enum Request<'a> {
         country: Country,
         language: Language,
     },
+    /// A lookup of a developer (artist) ID that includes all of the developer's apps.
+    Developer {
+        id: u64,
+        country: Country,
+        language: Language,
+    },
     Reviews {
         id: u64,
         country: Country,
@@ -53,21 +71,50 @@ pub enum Request<'a> {
     Ratings {
         id: u64,
         country: Country,
+        language: Option<Language>,
+    },
+    /// A top chart for a genre (charts for [`Genre::All`] include every genre).
+    GenreTop {
+        genre: Genre,
+        country: Country,
+        chart: params::chart::ChartType,
+    },
+    Suggest {
+        term: Cow<'a, str>,
+        country: Country,
+        language: Option<Language>,
     },
 }
 
 impl<'a> Request<'a> {
-    #[must_use]
-    pub const fn app(id: u64, country: Country) -> Self {
-        Self::App { id, country }
+    /// Fails if the country does not have an App Store market.
+    pub fn app(
+        id: u64,
+        country: Country,
+        language: Option<Language>,
+    ) -> Result<Self, markets::Error> {
+        markets::MarketCode::try_from(country)?;
+
+        Ok(Self::App {
+            id,
+            country,
+            language,
+        })
     }
 
-    pub fn search<S: Into<Cow<'a, str>>>(query: S, country: Country, language: Language) -> Self {
-        Self::Search {
+    /// Fails if the country does not have an App Store market.
+    pub fn search<S: Into<Cow<'a, str>>>(
+        query: S,
+        country: Country,
+        language: Language,
+    ) -> Result<Self, markets::Error> {
+        markets::MarketCode::try_from(country)?;
+
+        Ok(Self::Search {
             query: query.into(),
             country,
             language,
-        }
+        })
     }
 
     pub fn lookup_ids<I: IntoIterator<Item = u64>>(
@@ -97,6 +144,15 @@ impl<'a> Request<'a> {
         }
     }
 
+    #[must_use]
+    pub const fn developer(id: u64, country: Country, language: Language) -> Self {
+        Self::Developer {
+            id,
+            country,
+            language,
+        }
+    }
+
     #[must_use]
     pub const fn reviews(
         id: u64,
@@ -112,9 +168,76 @@ impl<'a> Request<'a> {
         }
     }
 
+    /// Fails if the country does not have an App Store market.
+    pub fn ratings(
+        id: u64,
+        country: Country,
+        language: Option<Language>,
+    ) -> Result<Self, markets::Error> {
+        markets::MarketCode::try_from(country)?;
+
+        Ok(Self::Ratings {
+            id,
+            country,
+            language,
+        })
+    }
+
+    #[must_use]
+    pub const fn genre_top(
+        genre: Genre,
+        country: Country,
+        chart: params::chart::ChartType,
+    ) -> Self {
+        Self::GenreTop {
+            genre,
+            country,
+            chart,
+        }
+    }
+
+    /// Fails if the country does not have an App Store market.
+    pub fn suggest<S: Into<Cow<'a, str>>>(
+        term: S,
+        country: Country,
+        language: Option<Language>,
+    ) -> Result<Self, markets::Error> {
+        markets::MarketCode::try_from(country)?;
+
+        Ok(Self::Suggest {
+            term: term.into(),
+            country,
+            language,
+        })
+    }
+
+    #[must_use]
+    pub const fn country(&self) -> Country {
+        match self {
+            Self::App { country, .. }
+            | Self::Search { country, .. }
+            | Self::LookupIds { country, .. }
+            | Self::LookupBundleIds { country, .. }
+            | Self::Developer { country, .. }
+            | Self::Reviews { country, .. }
+            | Self::Ratings { country, .. }
+            | Self::GenreTop { country, .. }
+            | Self::Suggest { country, .. } => *country,
+        }
+    }
+
     #[must_use]
-    pub const fn ratings(id: u64, country: Country) -> Self {
-        Self::Ratings { id, country }
+    pub const fn language(&self) -> Option<Language> {
+        match self {
+            Self::Search { language, .. }
+            | Self::LookupIds { language, .. }
+            | Self::LookupBundleIds { language, .. }
+            | Self::Developer { language, .. } => Some(*language),
+            Self::App { language, .. }
+            | Self::Ratings { language, .. }
+            | Self::Suggest { language, .. } => *language,
+            Self::Reviews { .. } | Self::GenreTop { .. } => None,
+        }
     }
 
     fn url(&self) -> String {
@@ -129,7 +252,7 @@ impl<'a> Request<'a> {
                 language,
             } => {
                 format!(
-                    "{LOOKUP_URL}?id={}&country={country}&entity=software&lang={language}",
+                    "{LOOKUP_URL}?id={}&country={country}&entity=software&lang={language}_{country}",
                     ids.iter()
                         .map(|id| (*id).to_string())
                         .collect::<Vec<_>>()
@@ -142,7 +265,7 @@ impl<'a> Request<'a> {
                 language,
             } => {
                 format!(
-                    "{LOOKUP_URL}?bundleId={}&country={country}&entity=software&lang={language}",
+                    "{LOOKUP_URL}?bundleId={}&country={country}&entity=software&lang={language}_{country}",
                     bundle_ids
                         .iter()
                         .map(|id| (*id).to_string())
@@ -150,6 +273,15 @@ impl<'a> Request<'a> {
                         .join(",")
                 )
             }
+            Self::Developer {
+                id,
+                country,
+                language,
+            } => {
+                format!(
+                    "{LOOKUP_URL}?id={id}&country={country}&entity=software&limit={LOOKUP_LIMIT}&lang={language}_{country}"
+                )
+            }
             Self::Reviews {
                 id,
                 country,
@@ -160,29 +292,47 @@ impl<'a> Request<'a> {
                     "https://itunes.apple.com/{country}/rss/customerreviews/page={page}/id={id}/sortby={sort}/json"
                 )
             }
-            Self::Ratings { id, country } => {
+            Self::Ratings { id, country, .. } => {
                 format!(
                     "https://itunes.apple.com/{country}/customer-reviews/id{id}?displayable-kind=11"
                 )
             }
+            Self::GenreTop {
+                genre,
+                country,
+                chart,
+            } => {
+                let genre_part = if *genre == Genre::All {
+                    String::new()
+                } else {
+                    format!("/genre={}", genre.id())
+                };
+
+                format!(
+                    "https://itunes.apple.com/{country}/rss/{chart}/limit={CHART_LIMIT}{genre_part}/json"
+                )
+            }
+            Self::Suggest { term, .. } => {
+                format!("{SUGGEST_URL}{}", urlencoding::encode(term))
+            }
         }
     }
 
-    fn headers(&self) -> headers::RequestHeaders {
-        match self {
-            Self::App { country, .. } => {
-                let store_id: markets::MarketCode = (*country).into();
-                let store_front =
-                    crate::request::headers::AppleStoreFrontHeader::new(store_id.0, 29, None);
-
-                crate::request::headers::RequestHeaders::new(Some(store_front), None)
+    fn headers(&self) -> Result<headers::RequestHeaders, markets::Error> {
+        Ok(match self {
+            Self::App {
+                country, language, ..
             }
+            | Self::Suggest {
+                country, language, ..
+            } => Self::localized_headers(*country, *language, 29)?,
             Self::Search {
                 country, language, ..
             } => {
-                let store_id: markets::MarketCode = (*country).into();
+                let store_id = markets::MarketCode::try_from(*country)?;
                 let store_front = crate::request::headers::AppleStoreFrontHeader::new(
                     store_id.0,
+                    None,
                     24,
                     Some("t:native".to_string()),
                 );
@@ -192,16 +342,87 @@ impl<'a> Request<'a> {
                     Some(language.to_string()),
                 )
             }
-            Self::LookupIds { .. } | Self::LookupBundleIds { .. } | Self::Reviews { .. } => {
-                crate::request::headers::RequestHeaders::default()
-            }
-            Self::Ratings { country, .. } => {
-                let store_id: markets::MarketCode = (*country).into();
-                let store_front =
-                    crate::request::headers::AppleStoreFrontHeader::new(store_id.0, 12, None);
+            Self::LookupIds { .. }
+            | Self::LookupBundleIds { .. }
+            | Self::Developer { .. }
+            | Self::Reviews { .. }
+            | Self::GenreTop { .. } => crate::request::headers::RequestHeaders::default(),
+            Self::Ratings {
+                country, language, ..
+            } => Self::localized_headers(*country, *language, 12)?,
+        })
+    }
 
-                crate::request::headers::RequestHeaders::new(Some(store_front), None)
-            }
+    /// Store front and language headers for requests where the language is optional.
+    fn localized_headers(
+        country: Country,
+        language: Option<Language>,
+        code: u8,
+    ) -> Result<headers::RequestHeaders, markets::Error> {
+        let store_id = markets::MarketCode::try_from(country)?;
+        let language_id = language
+            .and_then(|language| markets::LanguageCode::for_storefront(language, country))
+            .map(|language_code| language_code.0);
+        let store_front = headers::AppleStoreFrontHeader::new(store_id.0, language_id, code, None);
+
+        Ok(headers::RequestHeaders::new(
+            Some(store_front),
+            language.map(|language| language.to_string()),
+        ))
+    }
+
+    /// Build the request, failing if the country does not have an App Store market.
+    ///
+    /// Requests that do not use a store front header (lookups, reviews, and charts) never fail.
+    pub fn try_build_request(
+        &self,
+        timestamp: Option<DateTime<Utc>>,
+    ) -> Result<scraper_trail::request::Request<'a>, markets::Error> {
+        Ok(self.build_request_with_headers(timestamp, &self.headers()?))
+    }
+
+    fn build_request_with_headers(
+        &self,
+        timestamp: Option<DateTime<Utc>>,
+        headers: &headers::RequestHeaders,
+    ) -> scraper_trail::request::Request<'a> {
+        let url = self.url();
+        let headers = indexmap::IndexMap::from(headers);
+        let headers = if headers.is_empty() {
+            None
+        } else {
+            Some(headers)
+        };
+
+        scraper_trail::request::Request::new::<_, _, _, _, String>(
+            url, timestamp, None, headers, None,
+        )
+        .unwrap()
+    }
+
+    /// Lookup languages are sent as `{language}_{country}` (for example `fr_ca`), but older
+    /// requests only include the language.
+    fn parse_lookup_language(value: &str) -> Option<Language> {
+        value
+            .split_once('_')
+            .map_or(value, |(language, _)| language)
+            .parse()
+            .ok()
+    }
+
+    /// The developer ID for a lookup URL in the form used for [`Request::Developer`].
+    ///
+    /// Developer lookups are distinguished by requesting a single ID with the software entity and
+    /// the related-entity limit.
+    fn developer_lookup_id(query_params: &HashMap<Cow<'_, str>, Cow<'_, str>>) -> Option<u64> {
+        let limit = LOOKUP_LIMIT.to_string();
+
+        if query_params.get("entity").map(AsRef::as_ref) == Some("software")
+            && query_params.get("limit").map(AsRef::as_ref) == Some(limit.as_str())
+        {
+            query_params.get("id").and_then(|id| id.parse().ok())
+        } else {
+            None
         }
     }
 
@@ -223,7 +444,15 @@ impl bounded_static::IntoBoundedStatic for Request<'_> {
 
     fn into_static(self) -> Self::Static {
         match self {
-            Self::App { id, country } => Self::Static::App { id, country },
+            Self::App {
+                id,
+                country,
+                language,
+            } => Self::Static::App {
+                id,
+                country,
+                language,
+            },
             Self::Search {
                 query,
                 country,
@@ -251,6 +480,15 @@ impl bounded_static::IntoBoundedStatic for Request<'_> {
                 country,
                 language,
             },
+            Self::Developer {
+                id,
+                country,
+                language,
+            } => Self::Static::Developer {
+                id,
+                country,
+                language,
+            },
             Self::Reviews {
                 id,
                 country,
@@ -262,7 +500,33 @@ impl bounded_static::IntoBoundedStatic for Request<'_> {
                 sort,
                 page,
             },
-            Self::Ratings { id, country } => Self::Static::Ratings { id, country },
+            Self::Ratings {
+                id,
+                country,
+                language,
+            } => Self::Static::Ratings {
+                id,
+                country,
+                language,
+            },
+            Self::GenreTop {
+                genre,
+                country,
+                chart,
+            } => Self::Static::GenreTop {
+                genre,
+                country,
+                chart,
+            },
+            Self::Suggest {
+                term,
+                country,
+                language,
+            } => Self::Static::Suggest {
+                term: term.into_static(),
+                country,
+                language,
+            },
         }
     }
 }
@@ -272,9 +536,14 @@ impl bounded_static::ToBoundedStatic for Request<'_> {
 
     fn to_static(&self) -> Self::Static {
         match self {
-            Self::App { id, country } => Self::Static::App {
+            Self::App {
+                id,
+                country,
+                language,
+            } => Self::Static::App {
                 id: *id,
                 country: *country,
+                language: *language,
             },
             Self::Search {
                 query,
@@ -303,6 +572,15 @@ impl bounded_static::ToBoundedStatic for Request<'_> {
                 country: *country,
                 language: *language,
             },
+            Self::Developer {
+                id,
+                country,
+                language,
+            } => Self::Static::Developer {
+                id: *id,
+                country: *country,
+                language: *language,
+            },
             Self::Reviews {
                 id,
                 country,
@@ -314,31 +592,46 @@ impl bounded_static::ToBoundedStatic for Request<'_> {
                 sort: *sort,
                 page: *page,
             },
-            Self::Ratings { id, country } => Self::Static::Ratings {
+            Self::Ratings {
+                id,
+                country,
+                language,
+            } => Self::Static::Ratings {
                 id: *id,
                 country: *country,
+                language: *language,
+            },
+            Self::GenreTop {
+                genre,
+                country,
+                chart,
+            } => Self::Static::GenreTop {
+                genre: *genre,
+                country: *country,
+                chart: *chart,
+            },
+            Self::Suggest {
+                term,
+                country,
+                language,
+            } => Self::Static::Suggest {
+                term: term.to_static(),
+                country: *country,
+                language: *language,
             },
         }
     }
 }
 
 impl<'a> Params for Request<'a> {
+    /// Requests created with the constructors always have an App Store market. A request for a
+    /// country without one (constructed directly) is built without headers, so that the store
+    /// falls back to its default store front; use [`Request::try_build_request`] to detect this.
     fn build_request(
         &self,
         timestamp: Option<DateTime<Utc>>,
     ) -> scraper_trail::request::Request<'a> {
-        let url = self.url();
-        let headers = indexmap::IndexMap::from(&self.headers());
-        let headers = if headers.is_empty() {
-            None
-        } else {
-            Some(headers)
-        };
-
-        scraper_trail::request::Request::new::<_, _, _, _, String>(
-            url, timestamp, None, headers, None,
-        )
-        .unwrap()
+        self.build_request_with_headers(timestamp, &self.headers().unwrap_or_default())
     }
 
     fn parse_request(request: &scraper_trail::request::Request<'_>) -> Result<Self, ParseError> {
@@ -353,8 +646,18 @@ impl<'a> Params for Request<'a> {
             })
             .map_or(Ok(None), |value| value.map(Some))?;
 
-        let country: Option<Country> = apple_store_front_header
-            .and_then(|header| markets::MarketCode(header.store_id).try_into().ok());
+        let country = apple_store_front_header
+            .as_ref()
+            .map(|header| Country::try_from(markets::MarketCode(header.store_id)))
+            .transpose()
+            .map_err(|_| Self::header_error())?;
+
+        let store_front_language = apple_store_front_header
+            .as_ref()
+            .and_then(|header| header.language_id)
+            .map(|language_id| Language::try_from(markets::LanguageCode(language_id)))
+            .transpose()
+            .map_err(|_| Self::header_error())?;
 
         let accept_language_header = request
             .headers
@@ -362,6 +665,9 @@ impl<'a> Params for Request<'a> {
             .map(|header| header.parse::<Language>().map_err(|_| Self::header_error()))
             .map_or(Ok(None), |value| value.map(Some))?;
 
+        // Not every language has a store front language ID, so the Accept-Language header is preferred.
+        let language = accept_language_header.or(store_front_language);
+
         match url.as_str().strip_prefix(APP_URL) {
             Some(id_str) => {
                 let id = id_str.parse().map_err(|_| Self::url_error())?;
@@ -369,6 +675,7 @@ impl<'a> Params for Request<'a> {
                 Ok(Self::App {
                     id,
                     country: country.ok_or_else(Self::header_error)?,
+                    language,
                 })
             }
             None => match url.as_str().strip_prefix(SEARCH_URL) {
@@ -381,6 +688,24 @@ impl<'a> Params for Request<'a> {
                     Some(remainder) if remainder.starts_with("?id=") => {
                         let query_params = url.query_pairs().collect::<HashMap<_, _>>();
 
+                        let country = query_params
+                            .get("country")
+                            .and_then(|country| country.parse().ok())
+                            .ok_or_else(Self::header_error)?;
+
+                        let language = query_params
+                            .get("lang")
+                            .and_then(|language| Self::parse_lookup_language(language))
+                            .ok_or_else(Self::header_error)?;
+
+                        if let Some(id) = Self::developer_lookup_id(&query_params) {
+                            return Ok(Self::Developer {
+                                id,
+                                country,
+                                language,
+                            });
+                        }
+
                         let ids = query_params
                             .get("id")
                             .and_then(|ids| ids.split(',').map(|id| id.parse().ok()).collect())
@@ -388,14 +713,8 @@ impl<'a> Params for Request<'a> {
 
                         Ok(Self::LookupIds {
                             ids,
-                            country: query_params
-                                .get("country")
-                                .and_then(|country| country.parse().ok())
-                                .ok_or_else(Self::header_error)?,
-                            language: query_params
-                                .get("lang")
-                                .and_then(|language| language.parse().ok())
-                                .ok_or_else(Self::header_error)?,
+                            country,
+                            language,
                         })
                     }
                     Some(remainder) if remainder.starts_with("?bundleId=") => {
@@ -414,7 +733,7 @@ impl<'a> Params for Request<'a> {
                                 .ok_or_else(Self::header_error)?,
                             language: query_params
                                 .get("lang")
-                                .and_then(|language| language.parse().ok())
+                                .and_then(|language| Self::parse_lookup_language(language))
                                 .ok_or_else(Self::header_error)?,
                         })
                     }
@@ -460,9 +779,56 @@ impl<'a> Params for Request<'a> {
                                     .and_then(|id| id.as_str().parse().ok())
                                     .ok_or_else(Self::url_error)?;
 
-                                Ok(Self::Ratings { id, country })
+                                Ok(Self::Ratings {
+                                    id,
+                                    country,
+                                    language,
+                                })
                             }
-                            None => Err(Self::url_error()),
+                            None => match url.as_str().strip_prefix(SUGGEST_URL) {
+                                Some(term_str) => Ok(Self::Suggest {
+                                    term: urlencoding::decode(term_str)
+                                        .map_err(|_| Self::url_error())?
+                                        .into_owned()
+                                        .into(),
+                                    country: country.ok_or_else(Self::header_error)?,
+                                    language,
+                                }),
+                                None => match CHART_URL_RE.captures(url.as_str()) {
+                                    Some(captures) => {
+                                        let country = captures
+                                            .get(1)
+                                            .and_then(|country_code| {
+                                                country_code.as_str().parse().ok()
+                                            })
+                                            .ok_or_else(Self::url_error)?;
+
+                                        let chart = captures
+                                            .get(2)
+                                            .and_then(|chart| chart.as_str().parse().ok())
+                                            .ok_or_else(Self::url_error)?;
+
+                                        let genre = captures
+                                            .get(3)
+                                            .map(|id| {
+                                                id.as_str()
+                                                    .parse()
+                                                    .ok()
+                                                    .and_then(Genre::from_id)
+                                                    .ok_or_else(Self::url_error)
+                                            })
+                                            .transpose()?
+                                            .unwrap_or(Genre::All);
+
+                                        Ok(Self::GenreTop {
+                                            genre,
+                                            country,
+                                            chart,
+                                        })
+                                    }
+                                    None => Err(Self::url_error()),
+                                },
+                            },
                         },
                     },
                 },
@@ -470,3 +836,25 @@ impl<'a> Params for Request<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Request;
+    use app_store_access::{country::Country, language::Language};
+    use scraper_trail::request::params::Params;
+
+    #[test]
+    fn parse_lookup_requests() {
+        let requests = [
+            Request::developer(284_882_218, Country::UnitedStates, Language::English),
+            Request::lookup_ids([284_882_218], Country::UnitedStates, Language::English),
+            Request::lookup_ids([1, 2], Country::UnitedStates, Language::English),
+        ];
+
+        for request in requests {
+            let built = request.build_request(None);
+
+            assert_eq!(Request::parse_request(&built).unwrap(), request);
+        }
+    }
+}
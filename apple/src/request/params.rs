@@ -16,6 +16,12 @@ pub mod review {
         }
     }
 
+    impl serde::Serialize for SortOrder {
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_str(self.as_str())
+        }
+    }
+
     impl std::fmt::Display for SortOrder {
         fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
             f.write_str(self.as_str())
@@ -34,3 +40,50 @@ pub mod review {
         }
     }
 }
+
+pub mod chart {
+    /// A top chart in the RSS feeds.
+    #[derive(Copy, Clone, Debug, Default, Eq, Hash, PartialEq)]
+    pub enum ChartType {
+        #[default]
+        TopFree,
+        TopPaid,
+        TopGrossing,
+    }
+
+    impl ChartType {
+        #[must_use]
+        pub const fn as_str(&self) -> &'static str {
+            match self {
+                Self::TopFree => "topfreeapplications",
+                Self::TopPaid => "toppaidapplications",
+                Self::TopGrossing => "topgrossingapplications",
+            }
+        }
+    }
+
+    impl serde::Serialize for ChartType {
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_str(self.as_str())
+        }
+    }
+
+    impl std::fmt::Display for ChartType {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str(self.as_str())
+        }
+    }
+
+    impl std::str::FromStr for ChartType {
+        type Err = String;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            match s {
+                "topfreeapplications" | "top-free" => Ok(Self::TopFree),
+                "toppaidapplications" | "top-paid" => Ok(Self::TopPaid),
+                "topgrossingapplications" | "top-grossing" => Ok(Self::TopGrossing),
+                other => Err(other.to_string()),
+            }
+        }
+    }
+}
@@ -6,96 +6,532 @@ use std::sync::LazyLock;
 #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct Other([u8; 2]);
 
+/// A country, identified by its ISO 3166-1 alpha-2 code.
+///
+/// Every officially assigned ISO 3166-1 code has a named variant. `UnitedKingdom` represents `uk`,
+/// which is not an ISO code (the ISO code for the United Kingdom is `gb`, for `GreatBritain`) but
+/// is accepted by some APIs. Any other two-letter code is represented by `Other`.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub enum Country {
-    Austria,
+    Afghanistan,
+    AlandIslands,
+    Albania,
+    Algeria,
+    AmericanSamoa,
+    Andorra,
+    Angola,
+    Anguilla,
+    Antarctica,
+    AntiguaAndBarbuda,
+    Argentina,
+    Armenia,
+    Aruba,
     Australia,
+    Austria,
+    Azerbaijan,
+    Bahamas,
+    Bahrain,
+    Bangladesh,
+    Barbados,
+    Belarus,
     Belgium,
+    Belize,
+    Benin,
+    Bermuda,
+    Bhutan,
+    Bolivia,
+    BosniaAndHerzegovina,
+    Botswana,
+    BouvetIsland,
+    Brazil,
+    BritishIndianOceanTerritory,
+    BritishVirginIslands,
+    Brunei,
+    Bulgaria,
+    BurkinaFaso,
+    Burundi,
+    Cambodia,
+    Cameroon,
     Canada,
+    CapeVerde,
+    CaribbeanNetherlands,
+    CaymanIslands,
+    CentralAfricanRepublic,
+    Chad,
+    Chile,
     China,
+    ChristmasIsland,
+    CocosIslands,
+    Colombia,
+    Comoros,
+    CookIslands,
+    CostaRica,
+    Croatia,
+    Cuba,
+    Curacao,
+    Cyprus,
+    Czechia,
+    DemocraticRepublicOfTheCongo,
     Denmark,
+    Djibouti,
+    Dominica,
+    DominicanRepublic,
+    Ecuador,
+    Egypt,
+    ElSalvador,
+    EquatorialGuinea,
+    Eritrea,
+    Estonia,
+    Eswatini,
+    Ethiopia,
+    FalklandIslands,
+    FaroeIslands,
+    Fiji,
     Finland,
+    France,
+    FrenchGuiana,
+    FrenchPolynesia,
+    FrenchSouthernTerritories,
+    Gabon,
+    Gambia,
+    Georgia,
     Germany,
+    Ghana,
+    Gibraltar,
     GreatBritain,
     Greece,
+    Greenland,
+    Grenada,
+    Guadeloupe,
+    Guam,
+    Guatemala,
+    Guernsey,
+    Guinea,
+    GuineaBissau,
+    Guyana,
+    Haiti,
+    HeardIslandAndMcDonaldIslands,
+    Honduras,
     HongKong,
+    Hungary,
+    Iceland,
     India,
     Indonesia,
+    Iran,
+    Iraq,
     Ireland,
+    IsleOfMan,
+    Israel,
     Italy,
+    IvoryCoast,
+    Jamaica,
     Japan,
+    Jersey,
+    Jordan,
+    Kazakhstan,
+    Kenya,
+    Kiribati,
+    Kuwait,
+    Kyrgyzstan,
+    Laos,
+    Latvia,
+    Lebanon,
+    Lesotho,
+    Liberia,
+    Libya,
+    Liechtenstein,
+    Lithuania,
     Luxembourg,
+    Macao,
+    Madagascar,
+    Malawi,
     Malaysia,
+    Maldives,
+    Mali,
+    Malta,
+    MarshallIslands,
+    Martinique,
+    Mauritania,
+    Mauritius,
+    Mayotte,
     Mexico,
+    Micronesia,
+    Moldova,
+    Monaco,
+    Mongolia,
+    Montenegro,
+    Montserrat,
+    Morocco,
+    Mozambique,
+    Myanmar,
+    Namibia,
+    Nauru,
+    Nepal,
     Netherlands,
+    NewCaledonia,
     NewZealand,
+    Nicaragua,
+    Niger,
+    Nigeria,
+    Niue,
+    NorfolkIsland,
+    NorthKorea,
+    NorthMacedonia,
+    NorthernMarianaIslands,
     Norway,
+    Oman,
     Pakistan,
+    Palau,
+    Palestine,
+    Panama,
+    PapuaNewGuinea,
+    Paraguay,
+    Peru,
     Philippines,
+    PitcairnIslands,
     Poland,
     Portugal,
+    PuertoRico,
+    Qatar,
+    RepublicOfTheCongo,
+    Reunion,
+    Romania,
     Russia,
+    Rwanda,
+    SaintBarthelemy,
+    SaintHelena,
+    SaintKittsAndNevis,
+    SaintLucia,
+    SaintMartin,
+    SaintPierreAndMiquelon,
+    SaintVincentAndTheGrenadines,
+    Samoa,
+    SanMarino,
+    SaoTomeAndPrincipe,
     SaudiArabia,
+    Senegal,
+    Serbia,
+    Seychelles,
+    SierraLeone,
     Singapore,
+    SintMaarten,
+    Slovakia,
+    Slovenia,
+    SolomonIslands,
+    Somalia,
     SouthAfrica,
+    SouthGeorgiaAndTheSouthSandwichIslands,
     SouthKorea,
+    SouthSudan,
     Spain,
+    SriLanka,
+    Sudan,
+    Suriname,
+    SvalbardAndJanMayen,
     Sweden,
     Switzerland,
+    Syria,
     Taiwan,
+    Tajikistan,
+    Tanzania,
     Thailand,
+    TimorLeste,
+    Togo,
+    Tokelau,
+    Tonga,
+    TrinidadAndTobago,
+    Tunisia,
     Turkey,
+    Turkmenistan,
+    TurksAndCaicosIslands,
+    Tuvalu,
+    Uganda,
+    Ukraine,
     UnitedArabEmirates,
     UnitedKingdom,
     UnitedStates,
+    UnitedStatesMinorOutlyingIslands,
+    Uruguay,
+    UsVirginIslands,
+    Uzbekistan,
+    Vanuatu,
+    VaticanCity,
+    Venezuela,
     Vietnam,
+    WallisAndFutuna,
+    WesternSahara,
+    Yemen,
+    Zambia,
+    Zimbabwe,
     Other(Other),
 }
 
-const KNOWN_COUNTRIES: [(Country, &str); 40] = [
+const KNOWN_COUNTRIES: [(Country, &str); 250] = [
+    (Country::Andorra, "ad"),
     (Country::UnitedArabEmirates, "ae"),
+    (Country::Afghanistan, "af"),
+    (Country::AntiguaAndBarbuda, "ag"),
+    (Country::Anguilla, "ai"),
+    (Country::Albania, "al"),
+    (Country::Armenia, "am"),
+    (Country::Angola, "ao"),
+    (Country::Antarctica, "aq"),
+    (Country::Argentina, "ar"),
+    (Country::AmericanSamoa, "as"),
     (Country::Austria, "at"),
     (Country::Australia, "au"),
+    (Country::Aruba, "aw"),
+    (Country::AlandIslands, "ax"),
+    (Country::Azerbaijan, "az"),
+    (Country::BosniaAndHerzegovina, "ba"),
+    (Country::Barbados, "bb"),
+    (Country::Bangladesh, "bd"),
     (Country::Belgium, "be"),
+    (Country::BurkinaFaso, "bf"),
+    (Country::Bulgaria, "bg"),
+    (Country::Bahrain, "bh"),
+    (Country::Burundi, "bi"),
+    (Country::Benin, "bj"),
+    (Country::SaintBarthelemy, "bl"),
+    (Country::Bermuda, "bm"),
+    (Country::Brunei, "bn"),
+    (Country::Bolivia, "bo"),
+    (Country::CaribbeanNetherlands, "bq"),
+    (Country::Brazil, "br"),
+    (Country::Bahamas, "bs"),
+    (Country::Bhutan, "bt"),
+    (Country::BouvetIsland, "bv"),
+    (Country::Botswana, "bw"),
+    (Country::Belarus, "by"),
+    (Country::Belize, "bz"),
     (Country::Canada, "ca"),
+    (Country::CocosIslands, "cc"),
+    (Country::DemocraticRepublicOfTheCongo, "cd"),
+    (Country::CentralAfricanRepublic, "cf"),
+    (Country::RepublicOfTheCongo, "cg"),
     (Country::Switzerland, "ch"),
+    (Country::IvoryCoast, "ci"),
+    (Country::CookIslands, "ck"),
+    (Country::Chile, "cl"),
+    (Country::Cameroon, "cm"),
     (Country::China, "cn"),
+    (Country::Colombia, "co"),
+    (Country::CostaRica, "cr"),
+    (Country::Cuba, "cu"),
+    (Country::CapeVerde, "cv"),
+    (Country::Curacao, "cw"),
+    (Country::ChristmasIsland, "cx"),
+    (Country::Cyprus, "cy"),
+    (Country::Czechia, "cz"),
     (Country::Germany, "de"),
+    (Country::Djibouti, "dj"),
     (Country::Denmark, "dk"),
+    (Country::Dominica, "dm"),
+    (Country::DominicanRepublic, "do"),
+    (Country::Algeria, "dz"),
+    (Country::Ecuador, "ec"),
+    (Country::Estonia, "ee"),
+    (Country::Egypt, "eg"),
+    (Country::WesternSahara, "eh"),
+    (Country::Eritrea, "er"),
     (Country::Spain, "es"),
+    (Country::Ethiopia, "et"),
     (Country::Finland, "fi"),
+    (Country::Fiji, "fj"),
+    (Country::FalklandIslands, "fk"),
+    (Country::Micronesia, "fm"),
+    (Country::FaroeIslands, "fo"),
+    (Country::France, "fr"),
+    (Country::Gabon, "ga"),
     (Country::GreatBritain, "gb"),
+    (Country::Grenada, "gd"),
+    (Country::Georgia, "ge"),
+    (Country::FrenchGuiana, "gf"),
+    (Country::Guernsey, "gg"),
+    (Country::Ghana, "gh"),
+    (Country::Gibraltar, "gi"),
+    (Country::Greenland, "gl"),
+    (Country::Gambia, "gm"),
+    (Country::Guinea, "gn"),
+    (Country::Guadeloupe, "gp"),
+    (Country::EquatorialGuinea, "gq"),
     (Country::Greece, "gr"),
+    (Country::SouthGeorgiaAndTheSouthSandwichIslands, "gs"),
+    (Country::Guatemala, "gt"),
+    (Country::Guam, "gu"),
+    (Country::GuineaBissau, "gw"),
+    (Country::Guyana, "gy"),
     (Country::HongKong, "hk"),
+    (Country::HeardIslandAndMcDonaldIslands, "hm"),
+    (Country::Honduras, "hn"),
+    (Country::Croatia, "hr"),
+    (Country::Haiti, "ht"),
+    (Country::Hungary, "hu"),
     (Country::Indonesia, "id"),
     (Country::Ireland, "ie"),
+    (Country::Israel, "il"),
+    (Country::IsleOfMan, "im"),
     (Country::India, "in"),
+    (Country::BritishIndianOceanTerritory, "io"),
+    (Country::Iraq, "iq"),
+    (Country::Iran, "ir"),
+    (Country::Iceland, "is"),
     (Country::Italy, "it"),
+    (Country::Jersey, "je"),
+    (Country::Jamaica, "jm"),
+    (Country::Jordan, "jo"),
     (Country::Japan, "jp"),
+    (Country::Kenya, "ke"),
+    (Country::Kyrgyzstan, "kg"),
+    (Country::Cambodia, "kh"),
+    (Country::Kiribati, "ki"),
+    (Country::Comoros, "km"),
+    (Country::SaintKittsAndNevis, "kn"),
+    (Country::NorthKorea, "kp"),
     (Country::SouthKorea, "kr"),
+    (Country::Kuwait, "kw"),
+    (Country::CaymanIslands, "ky"),
+    (Country::Kazakhstan, "kz"),
+    (Country::Laos, "la"),
+    (Country::Lebanon, "lb"),
+    (Country::SaintLucia, "lc"),
+    (Country::Liechtenstein, "li"),
+    (Country::SriLanka, "lk"),
+    (Country::Liberia, "lr"),
+    (Country::Lesotho, "ls"),
+    (Country::Lithuania, "lt"),
     (Country::Luxembourg, "lu"),
+    (Country::Latvia, "lv"),
+    (Country::Libya, "ly"),
+    (Country::Morocco, "ma"),
+    (Country::Monaco, "mc"),
+    (Country::Moldova, "md"),
+    (Country::Montenegro, "me"),
+    (Country::SaintMartin, "mf"),
+    (Country::Madagascar, "mg"),
+    (Country::MarshallIslands, "mh"),
+    (Country::NorthMacedonia, "mk"),
+    (Country::Mali, "ml"),
+    (Country::Myanmar, "mm"),
+    (Country::Mongolia, "mn"),
+    (Country::Macao, "mo"),
+    (Country::NorthernMarianaIslands, "mp"),
+    (Country::Martinique, "mq"),
+    (Country::Mauritania, "mr"),
+    (Country::Montserrat, "ms"),
+    (Country::Malta, "mt"),
+    (Country::Mauritius, "mu"),
+    (Country::Maldives, "mv"),
+    (Country::Malawi, "mw"),
     (Country::Mexico, "mx"),
     (Country::Malaysia, "my"),
+    (Country::Mozambique, "mz"),
+    (Country::Namibia, "na"),
+    (Country::NewCaledonia, "nc"),
+    (Country::Niger, "ne"),
+    (Country::NorfolkIsland, "nf"),
+    (Country::Nigeria, "ng"),
+    (Country::Nicaragua, "ni"),
     (Country::Netherlands, "nl"),
     (Country::Norway, "no"),
+    (Country::Nepal, "np"),
+    (Country::Nauru, "nr"),
+    (Country::Niue, "nu"),
     (Country::NewZealand, "nz"),
+    (Country::Oman, "om"),
+    (Country::Panama, "pa"),
+    (Country::Peru, "pe"),
+    (Country::FrenchPolynesia, "pf"),
+    (Country::PapuaNewGuinea, "pg"),
     (Country::Philippines, "ph"),
     (Country::Pakistan, "pk"),
     (Country::Poland, "pl"),
+    (Country::SaintPierreAndMiquelon, "pm"),
+    (Country::PitcairnIslands, "pn"),
+    (Country::PuertoRico, "pr"),
+    (Country::Palestine, "ps"),
     (Country::Portugal, "pt"),
+    (Country::Palau, "pw"),
+    (Country::Paraguay, "py"),
+    (Country::Qatar, "qa"),
+    (Country::Reunion, "re"),
+    (Country::Romania, "ro"),
+    (Country::Serbia, "rs"),
     (Country::Russia, "ru"),
+    (Country::Rwanda, "rw"),
     (Country::SaudiArabia, "sa"),
+    (Country::SolomonIslands, "sb"),
+    (Country::Seychelles, "sc"),
+    (Country::Sudan, "sd"),
     (Country::Sweden, "se"),
     (Country::Singapore, "sg"),
+    (Country::SaintHelena, "sh"),
+    (Country::Slovenia, "si"),
+    (Country::SvalbardAndJanMayen, "sj"),
+    (Country::Slovakia, "sk"),
+    (Country::SierraLeone, "sl"),
+    (Country::SanMarino, "sm"),
+    (Country::Senegal, "sn"),
+    (Country::Somalia, "so"),
+    (Country::Suriname, "sr"),
+    (Country::SouthSudan, "ss"),
+    (Country::SaoTomeAndPrincipe, "st"),
+    (Country::ElSalvador, "sv"),
+    (Country::SintMaarten, "sx"),
+    (Country::Syria, "sy"),
+    (Country::Eswatini, "sz"),
+    (Country::TurksAndCaicosIslands, "tc"),
+    (Country::Chad, "td"),
+    (Country::FrenchSouthernTerritories, "tf"),
+    (Country::Togo, "tg"),
     (Country::Thailand, "th"),
+    (Country::Tajikistan, "tj"),
+    (Country::Tokelau, "tk"),
+    (Country::TimorLeste, "tl"),
+    (Country::Turkmenistan, "tm"),
+    (Country::Tunisia, "tn"),
+    (Country::Tonga, "to"),
     (Country::Turkey, "tr"),
+    (Country::TrinidadAndTobago, "tt"),
+    (Country::Tuvalu, "tv"),
     (Country::Taiwan, "tw"),
+    (Country::Tanzania, "tz"),
+    (Country::Ukraine, "ua"),
+    (Country::Uganda, "ug"),
     (Country::UnitedKingdom, "uk"),
+    (Country::UnitedStatesMinorOutlyingIslands, "um"),
     (Country::UnitedStates, "us"),
+    (Country::Uruguay, "uy"),
+    (Country::Uzbekistan, "uz"),
+    (Country::VaticanCity, "va"),
+    (Country::SaintVincentAndTheGrenadines, "vc"),
+    (Country::Venezuela, "ve"),
+    (Country::BritishVirginIslands, "vg"),
+    (Country::UsVirginIslands, "vi"),
+    (Country::Vietnam, "vn"),
+    (Country::Vanuatu, "vu"),
+    (Country::WallisAndFutuna, "wf"),
+    (Country::Samoa, "ws"),
+    (Country::Yemen, "ye"),
+    (Country::Mayotte, "yt"),
     (Country::SouthAfrica, "za"),
+    (Country::Zambia, "zm"),
+    (Country::Zimbabwe, "zw"),
 ];
 
 impl Country {
+    /// All countries with named variants, ordered by country code.
+    #[must_use]
+    pub fn known() -> Vec<Self> {
+        KNOWN_COUNTRIES.map(|(country, _)| country).to_vec()
+    }
+
+    /// Whether this country is identified by an officially assigned ISO 3166-1 alpha-2 code.
+    #[must_use]
+    pub const fn is_iso(&self) -> bool {
+        !matches!(self, Self::UnitedKingdom | Self::Other(_))
+    }
+
     pub fn as_str(&self) -> &str {
         static AS_STR: LazyLock<HashMap<Country, &'static str>> =
             LazyLock::new(|| KNOWN_COUNTRIES.iter().copied().collect());
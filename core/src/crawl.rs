@@ -0,0 +1,257 @@
+//! Breadth-first discovery of apps, with a frontier that can be saved to disk and resumed.
+//!
+//! A crawl starts from seed nodes (apps, developers, or search queries) and expands each node
+//! into the nodes it links to. Every node is visited at most once: nodes are marked as visited
+//! when they are queued, so a resumed crawl never repeats work that was already queued. The
+//! expansion itself is store-specific and is left to the caller, which should save the frontier
+//! after completing each node.
+use crate::index::Store;
+use std::collections::{BTreeSet, VecDeque};
+use std::path::Path;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("I/O error")]
+    Io(#[from] std::io::Error),
+    #[error("JSON error")]
+    Json(#[from] serde_json::Error),
+    #[error("Frontier belongs to a different store")]
+    StoreMismatch { expected: Store, found: Store },
+}
+
+/// Something that can be expanded into further nodes.
+#[derive(
+    Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, serde::Deserialize, serde::Serialize,
+)]
+#[serde(tag = "kind", content = "id", rename_all = "lowercase")]
+pub enum Node {
+    App(String),
+    Developer(String),
+    Query(String),
+}
+
+impl Node {
+    #[must_use]
+    pub const fn kind(&self) -> &'static str {
+        match self {
+            Self::App(_) => "app",
+            Self::Developer(_) => "developer",
+            Self::Query(_) => "query",
+        }
+    }
+
+    #[must_use]
+    pub fn id(&self) -> &str {
+        match self {
+            Self::App(id) | Self::Developer(id) | Self::Query(id) => id,
+        }
+    }
+}
+
+/// A queued node with the number of links followed from a seed to reach it.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct Entry {
+    pub node: Node,
+    pub depth: usize,
+}
+
+/// Limits for a crawl.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Budget {
+    /// Nodes more than this many links from a seed are not queued.
+    pub max_depth: usize,
+    /// The maximum number of nodes to expand in this run (if any).
+    pub max_nodes: Option<usize>,
+}
+
+impl Budget {
+    #[must_use]
+    pub fn allows(&self, expanded: usize) -> bool {
+        self.max_nodes.is_none_or(|max_nodes| expanded < max_nodes)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct Frontier {
+    store: Store,
+    queue: VecDeque<Entry>,
+    visited: BTreeSet<Node>,
+    expanded: usize,
+}
+
+impl Frontier {
+    #[must_use]
+    pub const fn new(store: Store) -> Self {
+        Self {
+            store,
+            queue: VecDeque::new(),
+            visited: BTreeSet::new(),
+            expanded: 0,
+        }
+    }
+
+    /// Load a saved frontier, or create an empty one if the file does not exist.
+    pub fn open<P: AsRef<Path>>(path: P, store: Store) -> Result<Self, Error> {
+        match std::fs::File::open(path) {
+            Ok(file) => {
+                let frontier: Self = serde_json::from_reader(std::io::BufReader::new(file))?;
+
+                if frontier.store == store {
+                    Ok(frontier)
+                } else {
+                    Err(Error::StoreMismatch {
+                        expected: store,
+                        found: frontier.store,
+                    })
+                }
+            }
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(Self::new(store)),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    /// Save the frontier, replacing the file only once the new contents have been written.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        let path = path.as_ref();
+        let mut temporary = path.as_os_str().to_os_string();
+        temporary.push(".tmp");
+
+        let file = std::fs::File::create(&temporary)?;
+        serde_json::to_writer(std::io::BufWriter::new(file), self)?;
+        std::fs::rename(&temporary, path)?;
+
+        Ok(())
+    }
+
+    #[must_use]
+    pub const fn store(&self) -> Store {
+        self.store
+    }
+
+    /// Queue a seed node, returning whether it had not already been visited.
+    pub fn seed(&mut self, node: Node) -> bool {
+        self.push(node, 0)
+    }
+
+    fn push(&mut self, node: Node, depth: usize) -> bool {
+        if self.visited.insert(node.clone()) {
+            self.queue.push_back(Entry { node, depth });
+
+            true
+        } else {
+            false
+        }
+    }
+
+    /// The next node to expand.
+    #[must_use]
+    pub fn next(&self) -> Option<&Entry> {
+        self.queue.front()
+    }
+
+    /// Remove the next node and queue the nodes it was expanded into.
+    ///
+    /// Returns the nodes that were newly queued.
+    pub fn complete<I: IntoIterator<Item = Node>>(
+        &mut self,
+        discovered: I,
+        budget: &Budget,
+    ) -> Vec<Node> {
+        self.queue
+            .pop_front()
+            .map(|entry| {
+                self.expanded += 1;
+
+                if entry.depth < budget.max_depth {
+                    discovered
+                        .into_iter()
+                        .filter_map(|node| self.push(node.clone(), entry.depth + 1).then_some(node))
+                        .collect()
+                } else {
+                    vec![]
+                }
+            })
+            .unwrap_or_default()
+    }
+
+    /// The number of nodes waiting to be expanded.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    #[must_use]
+    pub const fn expanded(&self) -> usize {
+        self.expanded
+    }
+
+    #[must_use]
+    pub fn is_visited(&self, node: &Node) -> bool {
+        self.visited.contains(node)
+    }
+
+    /// All nodes that have been queued (including those that have already been expanded).
+    pub fn visited(&self) -> impl Iterator<Item = &Node> {
+        self.visited.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn app(id: &str) -> Node {
+        Node::App(id.to_string())
+    }
+
+    #[test]
+    fn frontier_dedup_depth_and_resume() {
+        let budget = Budget {
+            max_depth: 1,
+            max_nodes: None,
+        };
+
+        let mut frontier = Frontier::new(Store::Google);
+
+        assert!(frontier.seed(app("a")));
+        assert!(!frontier.seed(app("a")));
+
+        assert_eq!(frontier.next().unwrap().node, app("a"));
+        assert_eq!(
+            frontier.complete(
+                [app("b"), app("a"), Node::Developer("1".to_string())],
+                &budget
+            ),
+            vec![app("b"), Node::Developer("1".to_string())]
+        );
+
+        // Nodes at the maximum depth are expanded but their links are not queued.
+        assert_eq!(frontier.next().unwrap().depth, 1);
+        assert!(frontier.complete([app("c")], &budget).is_empty());
+        assert!(!frontier.is_visited(&app("c")));
+
+        let path = std::env::temp_dir().join(format!("crawl-test-{}.json", std::process::id()));
+        frontier.save(&path).unwrap();
+
+        let resumed = Frontier::open(&path, Store::Google).unwrap();
+        assert_eq!(resumed, frontier);
+        assert_eq!(resumed.expanded(), 2);
+        assert_eq!(resumed.len(), 1);
+        assert!(Frontier::open(&path, Store::Apple).is_err());
+
+        std::fs::remove_file(&path).unwrap();
+
+        let limited = Budget {
+            max_depth: 0,
+            max_nodes: Some(2),
+        };
+
+        assert!(limited.allows(1));
+        assert!(!limited.allows(2));
+    }
+}
@@ -0,0 +1,222 @@
+//! Screenshot changes between archived snapshots of app listings.
+//!
+//! Developers change their screenshots when they run creative experiments, so comparing the
+//! screenshots listed in consecutive snapshots of a listing shows when each set of creatives was
+//! live. Listings are compared per country, language, and device type, since screenshots are
+//! localized and differ between devices.
+use crate::{
+    country::Country, index::Store, language::Language, media::Snapshot, model::ImageType,
+};
+use chrono::{DateTime, Utc};
+use std::collections::{BTreeMap, HashMap};
+
+/// A change in the screenshots for a device type between two snapshots.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Change {
+    pub store: Store,
+    pub app_id: String,
+    pub country: Country,
+    pub language: Option<Language>,
+    /// The device type (Apple only).
+    pub device: Option<String>,
+    pub before_timestamp: DateTime<Utc>,
+    pub after_timestamp: DateTime<Utc>,
+    /// Screenshot URLs in listing order.
+    pub before: Vec<String>,
+    pub after: Vec<String>,
+    /// The number of screenshots that were not listed before.
+    pub added: usize,
+    /// The number of screenshots that are no longer listed.
+    pub removed: usize,
+}
+
+impl Change {
+    /// Whether the same screenshots are listed in a different order.
+    #[must_use]
+    pub const fn reordered(&self) -> bool {
+        self.added == 0 && self.removed == 0
+    }
+}
+
+type ListingKey = (Store, String, Country, Option<Language>);
+
+/// The screenshots for each device type in a snapshot.
+type DeviceScreenshots = BTreeMap<Option<String>, Screenshots>;
+
+/// The screenshots for a device type in a snapshot, with the value each one is compared by.
+struct Screenshots {
+    urls: Vec<String>,
+    keys: Vec<String>,
+}
+
+/// Compare the screenshots of consecutive snapshots of each listing.
+///
+/// Screenshots are compared by `key`, which can map a URL to itself or to the content hash of the
+/// downloaded file (so that a new URL for an identical image is not reported as a change). A
+/// device type that is added or removed is reported with an empty list on the other side.
+#[must_use]
+pub fn changes<F: Fn(&str) -> String>(snapshots: &[Snapshot], key: F) -> Vec<Change> {
+    let mut by_listing = HashMap::<ListingKey, Vec<&Snapshot>>::new();
+
+    for snapshot in snapshots {
+        by_listing
+            .entry((
+                snapshot.store,
+                snapshot.app_id.clone(),
+                snapshot.country,
+                snapshot.language,
+            ))
+            .or_default()
+            .push(snapshot);
+    }
+
+    let mut changes = vec![];
+
+    for ((store, app_id, country, language), mut snapshots) in by_listing {
+        snapshots.sort_by_key(|snapshot| snapshot.timestamp);
+
+        let mut previous: Option<(DateTime<Utc>, DeviceScreenshots)> = None;
+
+        for snapshot in snapshots {
+            let mut current = BTreeMap::<Option<String>, Vec<(usize, &str)>>::new();
+
+            for asset in &snapshot.assets {
+                if asset.image_type == ImageType::Screenshot {
+                    current
+                        .entry(asset.device.clone())
+                        .or_default()
+                        .push((asset.position, &asset.url));
+                }
+            }
+
+            let current = current
+                .into_iter()
+                .map(|(device, mut urls)| {
+                    urls.sort_unstable();
+                    let urls = urls
+                        .into_iter()
+                        .map(|(_, url)| url.to_string())
+                        .collect::<Vec<_>>();
+
+                    (
+                        device,
+                        Screenshots {
+                            keys: urls.iter().map(|url| key(url.as_str())).collect(),
+                            urls,
+                        },
+                    )
+                })
+                .collect::<DeviceScreenshots>();
+
+            // The first snapshot of a listing is the baseline, not a change.
+            if let Some((before_timestamp, previous)) = &previous {
+                let mut devices = previous.keys().chain(current.keys()).collect::<Vec<_>>();
+                devices.sort();
+                devices.dedup();
+
+                for device in devices {
+                    let before = previous.get(device);
+                    let after = current.get(device);
+                    let before_keys = before.map_or(&[][..], |screenshots| &screenshots.keys);
+                    let after_keys = after.map_or(&[][..], |screenshots| &screenshots.keys);
+
+                    if before_keys != after_keys {
+                        changes.push(Change {
+                            store,
+                            app_id: app_id.clone(),
+                            country,
+                            language,
+                            device: device.clone(),
+                            before_timestamp: *before_timestamp,
+                            after_timestamp: snapshot.timestamp,
+                            before: before
+                                .map(|screenshots| screenshots.urls.clone())
+                                .unwrap_or_default(),
+                            after: after
+                                .map(|screenshots| screenshots.urls.clone())
+                                .unwrap_or_default(),
+                            added: after_keys
+                                .iter()
+                                .filter(|key| !before_keys.contains(key))
+                                .count(),
+                            removed: before_keys
+                                .iter()
+                                .filter(|key| !after_keys.contains(key))
+                                .count(),
+                        });
+                    }
+                }
+            }
+
+            previous = Some((snapshot.timestamp, current));
+        }
+    }
+
+    changes.sort_by(|a, b| {
+        (a.store, &a.app_id, a.after_timestamp, &a.device).cmp(&(
+            b.store,
+            &b.app_id,
+            b.after_timestamp,
+            &b.device,
+        ))
+    });
+
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::media::Asset;
+
+    fn snapshot(seconds: i64, screenshots: &[(&str, &str)]) -> Snapshot {
+        Snapshot {
+            store: Store::Apple,
+            app_id: "1".to_string(),
+            timestamp: DateTime::from_timestamp(seconds, 0).unwrap(),
+            country: Country::UnitedStates,
+            language: None,
+            assets: screenshots
+                .iter()
+                .enumerate()
+                .map(|(position, (device, url))| Asset {
+                    image_type: ImageType::Screenshot,
+                    device: Some((*device).to_string()),
+                    position,
+                    url: (*url).to_string(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn screenshot_changes() {
+        let snapshots = vec![
+            snapshot(3, &[("iphone", "b"), ("iphone", "a")]),
+            snapshot(1, &[("iphone", "a"), ("iphone", "b")]),
+            snapshot(2, &[("iphone", "a"), ("iphone", "b"), ("ipad", "c")]),
+            snapshot(4, &[("iphone", "b"), ("iphone", "d")]),
+        ];
+
+        let found = changes(&snapshots, ToString::to_string);
+
+        assert_eq!(found.len(), 4);
+
+        assert_eq!(found[0].device.as_deref(), Some("ipad"));
+        assert!(found[0].before.is_empty());
+        assert_eq!(found[0].added, 1);
+
+        assert_eq!(found[1].device.as_deref(), Some("ipad"));
+        assert!(found[1].after.is_empty());
+
+        assert_eq!(found[2].after, vec!["b", "a"]);
+        assert!(found[2].reordered());
+
+        assert_eq!(found[3].before, vec!["b", "a"]);
+        assert_eq!(found[3].after, vec!["b", "d"]);
+        assert_eq!((found[3].added, found[3].removed), (1, 1));
+
+        // When every screenshot has the same key only the added and removed device is reported.
+        assert_eq!(changes(&snapshots, |_| String::new()).len(), 2);
+    }
+}
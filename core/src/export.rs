@@ -0,0 +1,271 @@
+//! Typed Parquet export of archived data.
+//!
+//! Each store crate defines its own record types (with schemas derived from its models) and
+//! implements [`Record`] for them; this module provides the shared writer and column helpers.
+
+use crate::{country::Country, language::Language};
+use arrow::array::{
+    ArrayRef, BooleanArray, Float64Array, RecordBatch, StringArray, TimestampSecondArray,
+    UInt64Array,
+};
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef, TimeUnit};
+use chrono::{DateTime, Utc};
+use parquet::arrow::ArrowWriter;
+use std::fs::File;
+use std::path::Path;
+use std::sync::Arc;
+
+const DEFAULT_BATCH_SIZE: usize = 8192;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Arrow error")]
+    Arrow(#[from] arrow::error::ArrowError),
+    #[error("Parquet error")]
+    Parquet(#[from] parquet::errors::ParquetError),
+    #[error("I/O error")]
+    Io(#[from] std::io::Error),
+}
+
+/// A row type that can be written as an Arrow record batch.
+pub trait Record: Sized {
+    fn schema() -> SchemaRef;
+    fn columns(records: &[Self]) -> Vec<ArrayRef>;
+}
+
+/// Buffers records and writes them to a Parquet file in batches.
+pub struct Writer<R: Record> {
+    inner: ArrowWriter<File>,
+    buffer: Vec<R>,
+    batch_size: usize,
+    count: usize,
+}
+
+impl<R: Record> Writer<R> {
+    pub fn create<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let file = File::create(path)?;
+        let inner = ArrowWriter::try_new(file, R::schema(), None)?;
+
+        Ok(Self {
+            inner,
+            buffer: Vec::with_capacity(DEFAULT_BATCH_SIZE),
+            batch_size: DEFAULT_BATCH_SIZE,
+            count: 0,
+        })
+    }
+
+    pub fn write(&mut self, record: R) -> Result<(), Error> {
+        self.buffer.push(record);
+
+        if self.buffer.len() >= self.batch_size {
+            self.flush()?;
+        }
+
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> Result<(), Error> {
+        if !self.buffer.is_empty() {
+            let batch = RecordBatch::try_new(R::schema(), R::columns(&self.buffer))?;
+            self.inner.write(&batch)?;
+            self.count += self.buffer.len();
+            self.buffer.clear();
+        }
+
+        Ok(())
+    }
+
+    /// Write any remaining records and close the file, returning the total number of rows.
+    pub fn finish(mut self) -> Result<usize, Error> {
+        self.flush()?;
+        self.inner.close()?;
+
+        Ok(self.count)
+    }
+}
+
+#[must_use]
+pub fn string_field(name: &str, nullable: bool) -> Field {
+    Field::new(name, DataType::Utf8, nullable)
+}
+
+#[must_use]
+pub fn u64_field(name: &str, nullable: bool) -> Field {
+    Field::new(name, DataType::UInt64, nullable)
+}
+
+#[must_use]
+pub fn f64_field(name: &str, nullable: bool) -> Field {
+    Field::new(name, DataType::Float64, nullable)
+}
+
+#[must_use]
+pub fn bool_field(name: &str, nullable: bool) -> Field {
+    Field::new(name, DataType::Boolean, nullable)
+}
+
+#[must_use]
+pub fn timestamp_field(name: &str, nullable: bool) -> Field {
+    Field::new(
+        name,
+        DataType::Timestamp(TimeUnit::Second, Some("UTC".into())),
+        nullable,
+    )
+}
+
+pub fn string_column<R, F: Fn(&R) -> Option<&str>>(records: &[R], f: F) -> ArrayRef {
+    Arc::new(records.iter().map(f).collect::<StringArray>())
+}
+
+pub fn u64_column<R, F: Fn(&R) -> Option<u64>>(records: &[R], f: F) -> ArrayRef {
+    Arc::new(records.iter().map(f).collect::<UInt64Array>())
+}
+
+pub fn f64_column<R, F: Fn(&R) -> Option<f64>>(records: &[R], f: F) -> ArrayRef {
+    Arc::new(records.iter().map(f).collect::<Float64Array>())
+}
+
+pub fn bool_column<R, F: Fn(&R) -> Option<bool>>(records: &[R], f: F) -> ArrayRef {
+    Arc::new(records.iter().map(f).collect::<BooleanArray>())
+}
+
+pub fn timestamp_column<R, F: Fn(&R) -> Option<DateTime<Utc>>>(records: &[R], f: F) -> ArrayRef {
+    Arc::new(
+        records
+            .iter()
+            .map(|record| f(record).map(|timestamp| timestamp.timestamp()))
+            .collect::<TimestampSecondArray>()
+            .with_timezone("UTC"),
+    )
+}
+
+/// Request context shared by every exported row.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Snapshot {
+    pub timestamp: DateTime<Utc>,
+    pub country: Option<Country>,
+    pub language: Option<Language>,
+}
+
+impl Snapshot {
+    #[must_use]
+    pub fn fields() -> [Field; 3] {
+        [
+            timestamp_field("snapshot_timestamp", false),
+            string_field("country", true),
+            string_field("language", true),
+        ]
+    }
+
+    pub fn columns<R, F: Fn(&R) -> &Self>(records: &[R], f: F) -> [ArrayRef; 3] {
+        [
+            timestamp_column(records, |record| Some(f(record).timestamp)),
+            Arc::new(
+                records
+                    .iter()
+                    .map(|record| f(record).country.map(|country| country.to_string()))
+                    .collect::<StringArray>(),
+            ),
+            Arc::new(
+                records
+                    .iter()
+                    .map(|record| f(record).language.map(|language| language.to_string()))
+                    .collect::<StringArray>(),
+            ),
+        ]
+    }
+}
+
+/// Build a schema with the [`Snapshot`] fields followed by the given fields.
+#[must_use]
+pub fn snapshot_schema<const N: usize>(fields: [Field; N]) -> SchemaRef {
+    Arc::new(Schema::new(
+        Snapshot::fields()
+            .into_iter()
+            .chain(fields)
+            .collect::<Vec<_>>(),
+    ))
+}
+
+/// Build the columns for a schema created by [`snapshot_schema`].
+pub fn snapshot_columns<R, F: Fn(&R) -> &Snapshot, const N: usize>(
+    records: &[R],
+    f: F,
+    columns: [ArrayRef; N],
+) -> Vec<ArrayRef> {
+    Snapshot::columns(records, f)
+        .into_iter()
+        .chain(columns)
+        .collect()
+}
+
+/// Counts of rows written to each exported table.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Stats {
+    pub apps: usize,
+    pub developers: usize,
+    pub reviews: usize,
+    pub search_results: usize,
+    /// Archive files that could not be read or parsed (these are logged and skipped).
+    pub failed: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+    use std::sync::LazyLock;
+
+    struct Row {
+        id: String,
+        value: Option<u64>,
+    }
+
+    static SCHEMA: LazyLock<SchemaRef> = LazyLock::new(|| {
+        Arc::new(Schema::new(vec![
+            string_field("id", false),
+            u64_field("value", true),
+        ]))
+    });
+
+    impl Record for Row {
+        fn schema() -> SchemaRef {
+            SCHEMA.clone()
+        }
+
+        fn columns(records: &[Self]) -> Vec<ArrayRef> {
+            vec![
+                string_column(records, |row| Some(row.id.as_str())),
+                u64_column(records, |row| row.value),
+            ]
+        }
+    }
+
+    #[test]
+    fn write_and_read() -> Result<(), Box<dyn std::error::Error>> {
+        let path = std::env::temp_dir().join(format!("export-test-{}.parquet", std::process::id()));
+
+        let mut writer = Writer::<Row>::create(&path)?;
+        writer.write(Row {
+            id: "a".to_string(),
+            value: Some(1),
+        })?;
+        writer.write(Row {
+            id: "b".to_string(),
+            value: None,
+        })?;
+
+        assert_eq!(writer.finish()?, 2);
+
+        let reader = ParquetRecordBatchReaderBuilder::try_new(File::open(&path)?)?.build()?;
+        let rows = reader
+            .map(|batch| batch.map(|batch| batch.num_rows()))
+            .sum::<Result<usize, _>>()?;
+
+        assert_eq!(rows, 2);
+
+        std::fs::remove_file(path)?;
+
+        Ok(())
+    }
+}
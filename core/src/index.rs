@@ -0,0 +1,422 @@
+//! A local database index over the contents of a scraper-trail archive.
+//!
+//! Every archive file is ingested at most once: the file's path is recorded in the `files` table
+//! in the same transaction as the rows derived from it, so re-running a build only processes
+//! files that have been added since the previous run.
+use rusqlite::{Connection, OptionalExtension, Transaction, params};
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS files (
+    store TEXT NOT NULL,
+    path TEXT NOT NULL,
+    PRIMARY KEY (store, path)
+);
+CREATE TABLE IF NOT EXISTS snapshots (
+    id INTEGER PRIMARY KEY,
+    store TEXT NOT NULL,
+    path TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    timestamp TEXT NOT NULL,
+    country TEXT,
+    language TEXT
+);
+CREATE TABLE IF NOT EXISTS apps (
+    snapshot_id INTEGER NOT NULL REFERENCES snapshots(id),
+    store TEXT NOT NULL,
+    app_id TEXT NOT NULL,
+    bundle_id TEXT,
+    developer_id TEXT,
+    title TEXT NOT NULL,
+    developer_name TEXT
+);
+CREATE TABLE IF NOT EXISTS developers (
+    snapshot_id INTEGER NOT NULL REFERENCES snapshots(id),
+    store TEXT NOT NULL,
+    developer_id TEXT NOT NULL,
+    name TEXT,
+    app_id TEXT
+);
+CREATE TABLE IF NOT EXISTS reviews (
+    snapshot_id INTEGER NOT NULL REFERENCES snapshots(id),
+    store TEXT NOT NULL,
+    app_id TEXT NOT NULL,
+    review_id TEXT NOT NULL,
+    user_name TEXT NOT NULL,
+    score INTEGER NOT NULL,
+    timestamp TEXT,
+    text TEXT
+);
+CREATE TABLE IF NOT EXISTS search_results (
+    snapshot_id INTEGER NOT NULL REFERENCES snapshots(id),
+    store TEXT NOT NULL,
+    query TEXT,
+    position INTEGER NOT NULL,
+    app_id TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS apps_app_id ON apps (store, app_id);
+CREATE INDEX IF NOT EXISTS developers_developer_id ON developers (store, developer_id);
+CREATE INDEX IF NOT EXISTS reviews_app_id ON reviews (store, app_id);
+CREATE INDEX IF NOT EXISTS search_results_query ON search_results (store, query);
+";
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("SQLite error")]
+    Sqlite(#[from] rusqlite::Error),
+    #[error("I/O error")]
+    Io(#[from] std::io::Error),
+    #[error("Invalid path")]
+    InvalidPath(PathBuf),
+}
+
+#[derive(
+    Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, serde::Deserialize, serde::Serialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Store {
+    Apple,
+    Google,
+}
+
+impl Store {
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Apple => "apple",
+            Self::Google => "google",
+        }
+    }
+}
+
+impl FromStr for Store {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "apple" => Ok(Self::Apple),
+            "google" => Ok(Self::Google),
+            other => Err(other.to_string()),
+        }
+    }
+}
+
+impl Display for Store {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Metadata describing a single archive file.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SnapshotInfo<'a> {
+    pub kind: &'a str,
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    pub country: Option<crate::country::Country>,
+    pub language: Option<crate::language::Language>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct App<'a> {
+    pub app_id: &'a str,
+    pub bundle_id: Option<&'a str>,
+    pub developer_id: Option<&'a str>,
+    pub title: &'a str,
+    pub developer_name: Option<&'a str>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Developer<'a> {
+    pub developer_id: &'a str,
+    pub name: Option<&'a str>,
+    pub app_id: Option<&'a str>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Review<'a> {
+    pub app_id: &'a str,
+    pub review_id: &'a str,
+    pub user_name: &'a str,
+    pub score: u8,
+    pub timestamp: Option<chrono::DateTime<chrono::Utc>>,
+    pub text: Option<&'a str>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SearchResult<'a> {
+    pub query: Option<&'a str>,
+    pub position: usize,
+    pub app_id: &'a str,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Stats {
+    pub ingested: usize,
+    pub skipped: usize,
+    /// Files that could not be read or parsed.
+    pub failed: usize,
+}
+
+pub struct Index {
+    connection: Connection,
+}
+
+impl Index {
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        Self::from_connection(Connection::open(path)?)
+    }
+
+    pub fn open_in_memory() -> Result<Self, Error> {
+        Self::from_connection(Connection::open_in_memory()?)
+    }
+
+    fn from_connection(connection: Connection) -> Result<Self, Error> {
+        connection.execute_batch(SCHEMA)?;
+
+        Ok(Self { connection })
+    }
+
+    /// Check whether a file has been ingested (`path` is relative to the archive directory).
+    pub fn is_ingested(&self, store: Store, path: &Path) -> Result<bool, Error> {
+        let path = path_str(path)?;
+
+        Ok(self
+            .connection
+            .query_row(
+                "SELECT 1 FROM files WHERE store = ?1 AND path = ?2",
+                params![store.as_str(), path],
+                |_| Ok(()),
+            )
+            .optional()?
+            .is_some())
+    }
+
+    /// Record the rows for a single archive file.
+    ///
+    /// The rows and the file record are written in a single transaction, so a failure part-way
+    /// through leaves the file eligible for ingestion on the next run. As with
+    /// [`Self::is_ingested`], `path` is relative to the archive directory.
+    pub fn ingest<F: FnOnce(&Snapshot<'_>) -> Result<(), Error>>(
+        &mut self,
+        store: Store,
+        path: &Path,
+        info: &SnapshotInfo<'_>,
+        f: F,
+    ) -> Result<(), Error> {
+        let path = path_str(path)?;
+        let transaction = self.connection.transaction()?;
+
+        transaction.execute(
+            "INSERT INTO files (store, path) VALUES (?1, ?2)",
+            params![store.as_str(), path],
+        )?;
+
+        transaction.execute(
+            "INSERT INTO snapshots (store, path, kind, timestamp, country, language)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                store.as_str(),
+                path,
+                info.kind,
+                info.timestamp.to_rfc3339(),
+                info.country.map(|country| country.to_string()),
+                info.language.map(|language| language.to_string()),
+            ],
+        )?;
+
+        let snapshot = Snapshot {
+            transaction: &transaction,
+            store,
+            id: transaction.last_insert_rowid(),
+        };
+
+        f(&snapshot)?;
+
+        transaction.commit()?;
+
+        Ok(())
+    }
+
+    /// Run an arbitrary SQL query, returning the column names and all rows as text.
+    pub fn query(&self, sql: &str) -> Result<(Vec<String>, Vec<Vec<String>>), Error> {
+        let mut statement = self.connection.prepare(sql)?;
+        let columns = statement
+            .column_names()
+            .into_iter()
+            .map(std::string::ToString::to_string)
+            .collect::<Vec<_>>();
+        let column_count = columns.len();
+
+        let rows = statement
+            .query_map([], |row| {
+                (0..column_count)
+                    .map(|i| {
+                        Ok(match row.get_ref(i)? {
+                            rusqlite::types::ValueRef::Null => String::new(),
+                            rusqlite::types::ValueRef::Integer(value) => value.to_string(),
+                            rusqlite::types::ValueRef::Real(value) => value.to_string(),
+                            rusqlite::types::ValueRef::Text(value)
+                            | rusqlite::types::ValueRef::Blob(value) => {
+                                String::from_utf8_lossy(value).to_string()
+                            }
+                        })
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok((columns, rows))
+    }
+}
+
+pub struct Snapshot<'a> {
+    transaction: &'a Transaction<'a>,
+    store: Store,
+    id: i64,
+}
+
+impl Snapshot<'_> {
+    pub fn add_app(&self, app: &App<'_>) -> Result<(), Error> {
+        self.transaction.execute(
+            "INSERT INTO apps (snapshot_id, store, app_id, bundle_id, developer_id, title, developer_name)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                self.id,
+                self.store.as_str(),
+                app.app_id,
+                app.bundle_id,
+                app.developer_id,
+                app.title,
+                app.developer_name,
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    pub fn add_developer(&self, developer: &Developer<'_>) -> Result<(), Error> {
+        self.transaction.execute(
+            "INSERT INTO developers (snapshot_id, store, developer_id, name, app_id)
+                VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                self.id,
+                self.store.as_str(),
+                developer.developer_id,
+                developer.name,
+                developer.app_id,
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    pub fn add_review(&self, review: &Review<'_>) -> Result<(), Error> {
+        self.transaction.execute(
+            "INSERT INTO reviews (snapshot_id, store, app_id, review_id, user_name, score, timestamp, text)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                self.id,
+                self.store.as_str(),
+                review.app_id,
+                review.review_id,
+                review.user_name,
+                review.score,
+                review.timestamp.map(|timestamp| timestamp.to_rfc3339()),
+                review.text,
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    pub fn add_search_result(&self, search_result: &SearchResult<'_>) -> Result<(), Error> {
+        self.transaction.execute(
+            "INSERT INTO search_results (snapshot_id, store, query, position, app_id)
+                VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                self.id,
+                self.store.as_str(),
+                search_result.query,
+                search_result.position,
+                search_result.app_id,
+            ],
+        )?;
+
+        Ok(())
+    }
+}
+
+/// List the archive files in a scraper-trail archive directory in lexicographic order.
+///
+/// Paths are relative to the archive directory, so that the same file is recorded under the same
+/// path however the directory is specified. Files without a `.json` extension are ignored.
+pub fn archive_paths<P: AsRef<Path>>(root: P) -> Result<Vec<PathBuf>, Error> {
+    let mut paths = vec![];
+
+    for entry in std::fs::read_dir(root)? {
+        let entry = entry?;
+        let path = PathBuf::from(entry.file_name());
+
+        if entry.file_type()?.is_file()
+            && path
+                .extension()
+                .is_some_and(|extension| extension == "json")
+        {
+            paths.push(path);
+        }
+    }
+
+    paths.sort();
+
+    Ok(paths)
+}
+
+fn path_str(path: &Path) -> Result<&str, Error> {
+    path.to_str()
+        .ok_or_else(|| Error::InvalidPath(path.to_path_buf()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{App, Index, SnapshotInfo, Store};
+    use std::path::Path;
+
+    #[test]
+    fn ingest_once() {
+        let mut index = Index::open_in_memory().unwrap();
+        let path = Path::new("example.json");
+        let info = SnapshotInfo {
+            kind: "detail",
+            timestamp: chrono::DateTime::UNIX_EPOCH,
+            country: None,
+            language: None,
+        };
+
+        assert!(!index.is_ingested(Store::Google, path).unwrap());
+
+        index
+            .ingest(Store::Google, path, &info, |snapshot| {
+                snapshot.add_app(&App {
+                    app_id: "com.example",
+                    bundle_id: None,
+                    developer_id: Some("123"),
+                    title: "Example",
+                    developer_name: Some("Example Inc."),
+                })
+            })
+            .unwrap();
+
+        assert!(index.is_ingested(Store::Google, path).unwrap());
+        assert!(!index.is_ingested(Store::Apple, path).unwrap());
+
+        let (columns, rows) = index.query("SELECT app_id, title FROM apps").unwrap();
+
+        assert_eq!(columns, vec!["app_id", "title"]);
+        assert_eq!(
+            rows,
+            vec![vec!["com.example".to_string(), "Example".to_string()]]
+        );
+    }
+}
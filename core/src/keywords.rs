@@ -0,0 +1,177 @@
+//! Keyword discovery by recursive expansion of autocomplete suggestions.
+//!
+//! Starting from a seed term, each level of the expansion queries `term + " a"`, `term + " b"`,
+//! and so on for every term discovered at the previous level.
+use crate::{client::SuggestionClient, country::Country, language::Language};
+use std::collections::HashMap;
+
+pub const DEFAULT_ALPHABET: &str = "abcdefghijklmnopqrstuvwxyz";
+
+/// A discovered term, together with the depth at which it was first found and the term whose
+/// expansion produced it.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+pub struct Keyword {
+    pub term: String,
+    pub depth: usize,
+    pub parent: Option<String>,
+}
+
+/// The deduplicated result of an expansion, in discovery order.
+///
+/// The seed is always the first keyword (with a depth of zero and no parent).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Tree {
+    keywords: Vec<Keyword>,
+    indices: HashMap<String, usize>,
+}
+
+impl Tree {
+    fn new(seed: &str) -> Self {
+        Self {
+            keywords: vec![Keyword {
+                term: seed.to_string(),
+                depth: 0,
+                parent: None,
+            }],
+            indices: HashMap::from([(seed.to_string(), 0)]),
+        }
+    }
+
+    /// Add a term if it has not already been seen, returning whether it was new.
+    fn add(&mut self, term: &str, depth: usize, parent: &str) -> bool {
+        if self.indices.contains_key(term) {
+            false
+        } else {
+            self.indices.insert(term.to_string(), self.keywords.len());
+            self.keywords.push(Keyword {
+                term: term.to_string(),
+                depth,
+                parent: Some(parent.to_string()),
+            });
+
+            true
+        }
+    }
+
+    #[must_use]
+    pub fn seed(&self) -> &Keyword {
+        &self.keywords[0]
+    }
+
+    #[must_use]
+    pub fn keywords(&self) -> &[Keyword] {
+        &self.keywords
+    }
+
+    #[must_use]
+    pub fn get(&self, term: &str) -> Option<&Keyword> {
+        self.indices.get(term).map(|index| &self.keywords[*index])
+    }
+
+    pub fn children<'a>(&'a self, term: &'a str) -> impl Iterator<Item = &'a Keyword> {
+        self.keywords
+            .iter()
+            .filter(move |keyword| keyword.parent.as_deref() == Some(term))
+    }
+
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.keywords.len()
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.keywords.is_empty()
+    }
+}
+
+/// Expand a seed term to the given depth using the characters of `alphabet` as suffixes.
+///
+/// Note that the number of requests grows quickly: each level makes one request per character for
+/// every term found at the previous level.
+pub async fn expand<C: SuggestionClient + Sync>(
+    client: &C,
+    seed: &str,
+    depth: usize,
+    alphabet: &str,
+    country: Country,
+    language: Language,
+) -> Result<Tree, C::Error> {
+    let mut tree = Tree::new(seed);
+    let mut frontier = vec![seed.to_string()];
+
+    for level in 1..=depth {
+        let mut next = vec![];
+
+        for term in &frontier {
+            for character in alphabet.chars() {
+                let query = format!("{term} {character}");
+
+                for suggestion in client.lookup_suggestions(&query, country, language).await? {
+                    if tree.add(&suggestion, level, term) {
+                        next.push(suggestion);
+                    }
+                }
+            }
+        }
+
+        if next.is_empty() {
+            break;
+        }
+
+        frontier = next;
+    }
+
+    Ok(tree)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Prefix;
+
+    impl SuggestionClient for Prefix {
+        type Error = std::convert::Infallible;
+
+        fn lookup_suggestions(
+            &self,
+            query: &str,
+            _country: Country,
+            _language: Language,
+        ) -> impl std::future::Future<Output = Result<Vec<String>, Self::Error>> + Send {
+            // Suggest the query with its last character doubled, plus a fixed term.
+            let result = vec![
+                format!("{query}{}", &query[query.len() - 1..]),
+                "chess".to_string(),
+            ];
+
+            async move { Ok(result) }
+        }
+    }
+
+    #[tokio::test]
+    async fn expand_dedup_and_depth() {
+        let tree = expand(
+            &Prefix,
+            "chess",
+            2,
+            "ab",
+            Country::UnitedStates,
+            Language::English,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(tree.seed().term, "chess");
+        assert_eq!(tree.get("chess").unwrap().depth, 0);
+        assert_eq!(tree.get("chess aa").unwrap().depth, 1);
+        assert_eq!(tree.get("chess aa aa").unwrap().depth, 2);
+        assert_eq!(
+            tree.get("chess aa bb").unwrap().parent.as_deref(),
+            Some("chess aa")
+        );
+        assert_eq!(tree.children("chess").count(), 2);
+        assert_eq!(tree.len(), 1 + 2 + 4);
+    }
+}
@@ -410,6 +410,36 @@ pub mod language_code {
     }
 }
 
+pub mod language_code_option {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::borrow::Cow;
+
+    const EXPECTED: &str = "a language code";
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<super::Language>, D::Error> {
+        let code: Option<Cow<str>> = Deserialize::deserialize(deserializer)?;
+
+        code.map(|code| {
+            code.parse().map_err(|_| {
+                serde::de::Error::invalid_value(serde::de::Unexpected::Str(&code), &EXPECTED)
+            })
+        })
+        .transpose()
+    }
+
+    pub fn serialize<S: Serializer>(
+        value: &Option<super::Language>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        value
+            .as_ref()
+            .map(super::Language::as_str)
+            .serialize(serializer)
+    }
+}
+
 pub mod language_code_uppercase {
     use serde::{Deserialize, Deserializer, Serialize, Serializer};
     use std::borrow::Cow;
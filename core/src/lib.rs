@@ -3,6 +3,17 @@
 #![forbid(unsafe_code)]
 pub mod client;
 pub mod country;
+pub mod crawl;
+pub mod creative;
+pub mod export;
+pub mod index;
 pub mod js;
+pub mod keywords;
 pub mod language;
+pub mod localization;
+pub mod media;
 pub mod model;
+pub mod money;
+pub mod output;
+pub mod perceptual;
+pub mod rank;
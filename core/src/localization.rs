@@ -0,0 +1,133 @@
+//! Localization coverage of store listings.
+//!
+//! Both stores fall back to the default listing when an app has no localization for the requested
+//! language, so a listing is fetched once per language and each field is compared with the listing
+//! in a baseline language. A field that differs from the baseline is counted as localized.
+use crate::{index::Store, language::Language};
+
+/// The localizable parts of a store listing.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Listing {
+    pub title: String,
+    pub description: String,
+    pub screenshots: Vec<String>,
+}
+
+/// One cell of the localization matrix: an app in a single language.
+///
+/// The field flags are empty for the baseline language and when either listing is unavailable.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+pub struct Coverage {
+    pub store: Store,
+    pub app_id: String,
+    #[serde(with = "crate::language::language_code")]
+    pub language: Language,
+    pub baseline: bool,
+    pub available: bool,
+    /// Whether the app declares support for the language (only available for Apple).
+    pub declared: Option<bool>,
+    pub title: Option<bool>,
+    pub description: Option<bool>,
+    pub screenshots: Option<bool>,
+}
+
+impl Coverage {
+    /// Whether any field is localized.
+    #[must_use]
+    pub fn localized(&self) -> bool {
+        self.title == Some(true) || self.description == Some(true) || self.screenshots == Some(true)
+    }
+}
+
+/// Compare the listings for each language against the listing for the baseline language.
+///
+/// Missing listings (for example when the app is not found) are represented by `None`.
+#[must_use]
+pub fn coverage(
+    store: Store,
+    app_id: &str,
+    baseline: Language,
+    listings: &[(Language, Option<Listing>)],
+    declared: Option<&[Language]>,
+) -> Vec<Coverage> {
+    let baseline_listing = listings
+        .iter()
+        .find(|(language, _)| *language == baseline)
+        .and_then(|(_, listing)| listing.as_ref());
+
+    listings
+        .iter()
+        .map(|(language, listing)| {
+            let is_baseline = *language == baseline;
+            let compared = listing
+                .as_ref()
+                .zip(baseline_listing)
+                .filter(|_| !is_baseline);
+
+            Coverage {
+                store,
+                app_id: app_id.to_string(),
+                language: *language,
+                baseline: is_baseline,
+                available: listing.is_some(),
+                declared: declared.map(|declared| declared.contains(language)),
+                title: compared.map(|(listing, baseline)| listing.title != baseline.title),
+                description: compared
+                    .map(|(listing, baseline)| listing.description != baseline.description),
+                screenshots: compared
+                    .map(|(listing, baseline)| listing.screenshots != baseline.screenshots),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn listing(title: &str, description: &str, screenshots: &[&str]) -> Listing {
+        Listing {
+            title: title.to_string(),
+            description: description.to_string(),
+            screenshots: screenshots.iter().map(|url| (*url).to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn localization_coverage() {
+        let listings = [
+            (
+                Language::English,
+                Some(listing("Chess", "Play chess", &["a"])),
+            ),
+            (
+                Language::French,
+                Some(listing("Échecs", "Jouez aux échecs", &["a"])),
+            ),
+            (
+                Language::German,
+                Some(listing("Chess", "Play chess", &["a"])),
+            ),
+            (Language::Japanese, None),
+        ];
+
+        let coverage = coverage(
+            Store::Apple,
+            "1",
+            Language::English,
+            &listings,
+            Some(&[Language::English, Language::French]),
+        );
+
+        assert!(coverage[0].baseline);
+        assert_eq!(coverage[0].title, None);
+        assert_eq!(coverage[1].title, Some(true));
+        assert_eq!(coverage[1].screenshots, Some(false));
+        assert_eq!(coverage[1].declared, Some(true));
+        assert!(coverage[1].localized());
+        assert!(!coverage[2].localized());
+        assert_eq!(coverage[2].declared, Some(false));
+        assert!(!coverage[3].available);
+        assert_eq!(coverage[3].description, None);
+    }
+}
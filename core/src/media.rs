@@ -0,0 +1,529 @@
+//! Content-addressed storage for downloaded icons, screenshots, and preview videos.
+//!
+//! Files are stored under the SHA-256 hash of their contents, so an asset that is listed in many
+//! snapshots (or by many apps) is only stored once. Preview videos that are served as HLS streams
+//! are stored as the concatenated segments of their highest-bandwidth variant. A manifest (one JSON object per line) records
+//! which assets each app listed in each archived snapshot, and which file each asset resolved to.
+use crate::{country::Country, index::Store, language::Language, model::ImageType};
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write as _;
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+
+const OBJECTS_DIR: &str = "objects";
+const MANIFEST_FILE: &str = "manifest.jsonl";
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("I/O error")]
+    Io(#[from] std::io::Error),
+    #[error("JSON error")]
+    Json(#[from] serde_json::Error),
+}
+
+/// An asset listed on an app page.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Asset {
+    pub image_type: ImageType,
+    /// The device type the asset is shown for (Apple only).
+    pub device: Option<String>,
+    /// The position of the asset among assets of the same type and device.
+    pub position: usize,
+    pub url: String,
+}
+
+/// The assets listed for an app in a single archived snapshot.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Snapshot {
+    pub store: Store,
+    pub app_id: String,
+    pub timestamp: DateTime<Utc>,
+    pub country: Country,
+    /// The requested listing language (if any).
+    pub language: Option<Language>,
+    pub assets: Vec<Asset>,
+}
+
+/// A file in the store.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct Object {
+    /// Hex-encoded SHA-256 hash of the contents.
+    pub hash: String,
+    pub extension: String,
+    pub size: usize,
+}
+
+/// A manifest line.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct Entry {
+    pub store: Store,
+    pub app_id: String,
+    pub timestamp: DateTime<Utc>,
+    #[serde(with = "crate::country::country_code")]
+    pub country: Country,
+    #[serde(default, with = "crate::language::language_code_option")]
+    pub language: Option<Language>,
+    pub image_type: ImageType,
+    pub device: Option<String>,
+    pub position: usize,
+    pub url: String,
+    #[serde(flatten)]
+    pub object: Object,
+}
+
+impl Entry {
+    #[must_use]
+    pub fn new(snapshot: &Snapshot, asset: &Asset, object: Object) -> Self {
+        Self {
+            store: snapshot.store,
+            app_id: snapshot.app_id.clone(),
+            timestamp: snapshot.timestamp,
+            country: snapshot.country,
+            language: snapshot.language,
+            image_type: asset.image_type,
+            device: asset.device.clone(),
+            position: asset.position,
+            url: asset.url.clone(),
+            object,
+        }
+    }
+}
+
+/// Identifies a snapshot: the same app can be archived at the same time for several listings.
+type SnapshotKey = (Store, String, DateTime<Utc>, Country, Option<Language>);
+
+pub struct MediaStore {
+    root: PathBuf,
+    entries: Vec<Entry>,
+    by_url: HashMap<String, usize>,
+    snapshots: HashSet<SnapshotKey>,
+}
+
+impl MediaStore {
+    /// Open a store, creating the directory if it does not exist.
+    pub fn open<P: AsRef<Path>>(root: P) -> Result<Self, Error> {
+        let root = root.as_ref().to_path_buf();
+        std::fs::create_dir_all(root.join(OBJECTS_DIR))?;
+
+        let mut store = Self {
+            root,
+            entries: vec![],
+            by_url: HashMap::new(),
+            snapshots: HashSet::new(),
+        };
+
+        match std::fs::File::open(store.manifest_path()) {
+            Ok(file) => {
+                for line in std::io::BufReader::new(file).lines() {
+                    let line = line?;
+
+                    if !line.trim().is_empty() {
+                        store.add_entry(serde_json::from_str(&line)?);
+                    }
+                }
+            }
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {}
+            Err(error) => return Err(error.into()),
+        }
+
+        Ok(store)
+    }
+
+    #[must_use]
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn manifest_path(&self) -> PathBuf {
+        self.root.join(MANIFEST_FILE)
+    }
+
+    fn add_entry(&mut self, entry: Entry) {
+        self.by_url.insert(entry.url.clone(), self.entries.len());
+        self.snapshots.insert((
+            entry.store,
+            entry.app_id.clone(),
+            entry.timestamp,
+            entry.country,
+            entry.language,
+        ));
+        self.entries.push(entry);
+    }
+
+    /// All manifest entries, in the order they were recorded.
+    #[must_use]
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    /// Whether the assets for a snapshot have already been recorded.
+    #[must_use]
+    pub fn contains_snapshot(&self, snapshot: &Snapshot) -> bool {
+        self.snapshots.contains(&(
+            snapshot.store,
+            snapshot.app_id.clone(),
+            snapshot.timestamp,
+            snapshot.country,
+            snapshot.language,
+        ))
+    }
+
+    /// The stored file for a URL that has already been downloaded.
+    ///
+    /// Store asset URLs are versioned, so the contents for a URL are not expected to change.
+    #[must_use]
+    pub fn object_for_url(&self, url: &str) -> Option<&Object> {
+        self.by_url
+            .get(url)
+            .map(|index| &self.entries[*index].object)
+    }
+
+    #[must_use]
+    pub fn path(&self, object: &Object) -> PathBuf {
+        let prefix = object.hash.get(0..2).unwrap_or(&object.hash);
+
+        self.root
+            .join(OBJECTS_DIR)
+            .join(prefix)
+            .join(format!("{}.{}", object.hash, object.extension))
+    }
+
+    /// Add a file to the store (if it is not already present).
+    pub fn insert(&self, contents: &[u8], extension: &str) -> Result<Object, Error> {
+        let hash =
+            Sha256::digest(contents)
+                .iter()
+                .fold(String::with_capacity(64), |mut hash, byte| {
+                    // Writing to a `String` cannot fail.
+                    let _ = write!(hash, "{byte:02x}");
+                    hash
+                });
+
+        let object = Object {
+            hash,
+            extension: extension.to_string(),
+            size: contents.len(),
+        };
+
+        let path = self.path(&object);
+
+        if !path.exists() {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+
+            let mut temporary = path.as_os_str().to_os_string();
+            temporary.push(".tmp");
+
+            std::fs::write(&temporary, contents)?;
+            std::fs::rename(&temporary, &path)?;
+        }
+
+        Ok(object)
+    }
+
+    /// Append the entries for a snapshot to the manifest.
+    ///
+    /// Entries should only be recorded once every asset in the snapshot has been stored, so that
+    /// a snapshot with failed downloads is retried.
+    pub fn record<I: IntoIterator<Item = Entry>>(&mut self, entries: I) -> Result<(), Error> {
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.manifest_path())?;
+        let mut writer = std::io::BufWriter::new(file);
+
+        for entry in entries {
+            serde_json::to_writer(&mut writer, &entry)?;
+            writeln!(writer)?;
+            self.add_entry(entry);
+        }
+
+        writer.flush()?;
+
+        Ok(())
+    }
+}
+
+/// Pick a file extension for a downloaded asset from its content type, falling back to the URL.
+#[must_use]
+pub fn extension(url: &str, content_type: Option<&str>) -> String {
+    let from_content_type = content_type
+        .and_then(|content_type| content_type.split(';').next())
+        .and_then(|mime| match mime.trim() {
+            "image/png" => Some("png"),
+            "image/jpeg" => Some("jpg"),
+            "image/webp" => Some("webp"),
+            "image/gif" => Some("gif"),
+            "video/mp4" => Some("mp4"),
+            "video/mp2t" => Some("ts"),
+            _ => None,
+        });
+
+    from_content_type.map_or_else(
+        || {
+            let path = url.split(['?', '#']).next().unwrap_or(url);
+            let file_name = path.rsplit('/').next().unwrap_or(path);
+
+            file_name
+                .rsplit_once('.')
+                .map(|(_, extension)| extension.to_ascii_lowercase())
+                .filter(|extension| {
+                    !extension.is_empty()
+                        && extension.len() <= 5
+                        && extension.chars().all(|c| c.is_ascii_alphanumeric())
+                })
+                .unwrap_or_else(|| "bin".to_string())
+        },
+        ToString::to_string,
+    )
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum PlaylistError {
+    #[error("Invalid HLS playlist")]
+    Invalid,
+    #[error("HLS playlist has no variants or segments")]
+    Empty,
+    #[error("Encrypted HLS playlists are not supported")]
+    Encrypted,
+}
+
+/// An HLS playlist (Apple preview videos are served as HLS streams).
+///
+/// Only the parts needed to download a single variant are parsed.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Playlist<'a> {
+    /// The variants of a stream at different bitrates (there is always at least one).
+    Multivariant(Vec<Variant<'a>>),
+    /// The segments of a single variant (there is always at least one).
+    Media {
+        /// The initialization section for fragmented MP4 segments.
+        init: Option<&'a str>,
+        segments: Vec<&'a str>,
+    },
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Variant<'a> {
+    /// Peak bit rate in bits per second.
+    pub bandwidth: u64,
+    pub uri: &'a str,
+}
+
+impl<'a> Playlist<'a> {
+    #[must_use]
+    pub fn is_playlist(contents: &[u8]) -> bool {
+        contents
+            .strip_prefix(b"\xef\xbb\xbf")
+            .unwrap_or(contents)
+            .trim_ascii_start()
+            .starts_with(b"#EXTM3U")
+    }
+
+    pub fn parse(contents: &'a str) -> Result<Self, PlaylistError> {
+        let mut lines = contents
+            .trim_start_matches('\u{feff}')
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty());
+
+        if lines.next() != Some("#EXTM3U") {
+            return Err(PlaylistError::Invalid);
+        }
+
+        let mut variants = vec![];
+        let mut init = None;
+        let mut segments = vec![];
+        let mut bandwidth = None;
+
+        for line in lines {
+            if let Some(attributes) = line.strip_prefix("#EXT-X-STREAM-INF:") {
+                bandwidth = Some(
+                    Self::attribute(attributes, "BANDWIDTH")
+                        .and_then(|value| value.parse().ok())
+                        .ok_or(PlaylistError::Invalid)?,
+                );
+            } else if let Some(attributes) = line.strip_prefix("#EXT-X-MAP:") {
+                init = Some(Self::attribute(attributes, "URI").ok_or(PlaylistError::Invalid)?);
+            } else if let Some(attributes) = line.strip_prefix("#EXT-X-KEY:") {
+                if Self::attribute(attributes, "METHOD") != Some("NONE") {
+                    return Err(PlaylistError::Encrypted);
+                }
+            } else if !line.starts_with('#') {
+                match bandwidth.take() {
+                    Some(bandwidth) => variants.push(Variant {
+                        bandwidth,
+                        uri: line,
+                    }),
+                    None => segments.push(line),
+                }
+            }
+        }
+
+        if !variants.is_empty() {
+            Ok(Self::Multivariant(variants))
+        } else if !segments.is_empty() {
+            Ok(Self::Media { init, segments })
+        } else {
+            Err(PlaylistError::Empty)
+        }
+    }
+
+    /// The variant with the highest bandwidth (for multivariant playlists).
+    #[must_use]
+    pub fn best_variant(&self) -> Option<&Variant<'a>> {
+        match self {
+            Self::Multivariant(variants) => variants.iter().max_by_key(|variant| variant.bandwidth),
+            Self::Media { .. } => None,
+        }
+    }
+
+    /// The file extension for the concatenated segments (for media playlists).
+    #[must_use]
+    pub const fn extension(&self) -> Option<&'static str> {
+        match self {
+            Self::Multivariant(_) => None,
+            Self::Media { init: Some(_), .. } => Some("mp4"),
+            Self::Media { init: None, .. } => Some("ts"),
+        }
+    }
+
+    fn attribute<'b>(attributes: &'b str, name: &str) -> Option<&'b str> {
+        let mut rest = attributes;
+
+        while let Some((key, value)) = rest.split_once('=') {
+            let (value, next) = match value.strip_prefix('"') {
+                Some(quoted) => {
+                    let (value, next) = quoted.split_once('"')?;
+                    (value, next.strip_prefix(',').unwrap_or(next))
+                }
+                None => value.split_once(',').unwrap_or((value, "")),
+            };
+
+            if key.trim() == name {
+                return Some(value);
+            }
+
+            rest = next;
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn store_dedup_and_reopen() {
+        let root = std::env::temp_dir().join(format!("media-test-{}", std::process::id()));
+        let mut store = MediaStore::open(&root).unwrap();
+
+        let snapshot = Snapshot {
+            store: Store::Google,
+            app_id: "com.example".to_string(),
+            timestamp: DateTime::from_timestamp(1_700_000_000, 0).unwrap(),
+            country: Country::UnitedStates,
+            language: None,
+            assets: vec![Asset {
+                image_type: ImageType::Icon,
+                device: None,
+                position: 0,
+                url: "https://example.com/icon".to_string(),
+            }],
+        };
+
+        let first = store.insert(b"icon", "png").unwrap();
+        let second = store.insert(b"icon", "png").unwrap();
+
+        assert_eq!(first, second);
+        assert_eq!(
+            first.hash,
+            "c2d4b446a44ce54fab8e01150e24dd24f3d850c7c14dcfe31f6321341dd86874"
+        );
+        assert_eq!(std::fs::read(store.path(&first)).unwrap(), b"icon");
+
+        assert!(!store.contains_snapshot(&snapshot));
+        store
+            .record([Entry::new(&snapshot, &snapshot.assets[0], first.clone())])
+            .unwrap();
+
+        let reopened = MediaStore::open(&root).unwrap();
+        assert!(reopened.contains_snapshot(&snapshot));
+        assert!(!reopened.contains_snapshot(&Snapshot {
+            country: Country::Canada,
+            ..snapshot
+        }));
+        assert_eq!(
+            reopened.object_for_url("https://example.com/icon"),
+            Some(&first)
+        );
+        assert_eq!(reopened.entries().len(), 1);
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn extension_from_content_type_or_url() {
+        assert_eq!(extension("https://example.com/a", Some("image/png")), "png");
+        assert_eq!(
+            extension("https://example.com/a.JPG?x=1", Some("text/plain")),
+            "jpg"
+        );
+        assert_eq!(extension("https://example.com/a", None), "bin");
+    }
+
+    #[test]
+    fn parse_hls_playlists() {
+        let multivariant = "#EXTM3U\n\
+            #EXT-X-STREAM-INF:BANDWIDTH=1200000,CODECS=\"avc1.4d401f,mp4a.40.2\",RESOLUTION=640x360\n\
+            low/index.m3u8\n\
+            #EXT-X-STREAM-INF:CODECS=\"avc1.640028,mp4a.40.2\",BANDWIDTH=5400000\n\
+            high/index.m3u8\n\
+            #EXT-X-I-FRAME-STREAM-INF:BANDWIDTH=90000,URI=\"iframes.m3u8\"\n";
+
+        assert!(Playlist::is_playlist(multivariant.as_bytes()));
+        assert!(!Playlist::is_playlist(b"\x89PNG"));
+
+        let playlist = Playlist::parse(multivariant).unwrap();
+        assert_eq!(
+            playlist.best_variant(),
+            Some(&Variant {
+                bandwidth: 5_400_000,
+                uri: "high/index.m3u8"
+            })
+        );
+        assert_eq!(playlist.extension(), None);
+
+        let media = "#EXTM3U\n#EXT-X-TARGETDURATION:6\n#EXT-X-MAP:URI=\"init.mp4\"\n\
+            #EXTINF:6.0,\nsegment0.m4s\n#EXTINF:4.5,\nsegment1.m4s\n#EXT-X-ENDLIST\n";
+
+        let playlist = Playlist::parse(media).unwrap();
+        assert_eq!(
+            playlist,
+            Playlist::Media {
+                init: Some("init.mp4"),
+                segments: vec!["segment0.m4s", "segment1.m4s"]
+            }
+        );
+        assert_eq!(playlist.best_variant(), None);
+        assert_eq!(playlist.extension(), Some("mp4"));
+
+        assert!(matches!(
+            Playlist::parse("#EXTM3U\n#EXT-X-KEY:METHOD=AES-128,URI=\"key\"\n#EXTINF:6.0,\na.ts\n"),
+            Err(PlaylistError::Encrypted)
+        ));
+        assert!(matches!(
+            Playlist::parse("#EXTM3U\n#EXT-X-ENDLIST\n"),
+            Err(PlaylistError::Empty)
+        ));
+        assert!(matches!(
+            Playlist::parse("segment.ts\n"),
+            Err(PlaylistError::Invalid)
+        ));
+    }
+}
@@ -1,8 +1,13 @@
-#[derive(Clone, Copy, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[derive(
+    Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, serde::Deserialize, serde::Serialize,
+)]
+#[serde(rename_all = "lowercase")]
 pub enum ImageType {
     Icon,
     Header,
     Screenshot,
+    /// A preview video (for Apple the highest-bandwidth variant of an HLS stream).
+    Video,
 }
 
 impl ImageType {
@@ -12,6 +17,7 @@ impl ImageType {
             Self::Icon => 0,
             Self::Header => 1,
             Self::Screenshot => 2,
+            Self::Video => 3,
         }
     }
 }
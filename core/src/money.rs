@@ -0,0 +1,403 @@
+//! Store-independent prices and currency conversion.
+//!
+//! Amounts are stored as a whole number of millionths of a currency unit (the same precision as
+//! Google's "micros" prices), so prices from both stores can be compared without floating-point
+//! rounding.
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::path::Path;
+use std::str::FromStr;
+
+const MICROS_PER_UNIT: i64 = 1_000_000;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Invalid currency code")]
+    InvalidCurrencyCode(String),
+    #[error("Invalid amount")]
+    InvalidAmount(String),
+    #[error("Invalid rate for base currency")]
+    InvalidBaseRate(CurrencyCode),
+    #[error("I/O error")]
+    Io(#[from] std::io::Error),
+    #[error("JSON error")]
+    Json(#[from] serde_json::Error),
+}
+
+/// An ISO 4217 currency code (for example `USD`).
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct CurrencyCode([u8; 3]);
+
+impl CurrencyCode {
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        // Codes are only constructed from ASCII letters, so this is always valid UTF-8.
+        std::str::from_utf8(&self.0).unwrap_or_default()
+    }
+}
+
+impl FromStr for CurrencyCode {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        <[u8; 3]>::try_from(s.as_bytes())
+            .ok()
+            .filter(|bytes| bytes.iter().all(u8::is_ascii_uppercase))
+            .map(Self)
+            .ok_or_else(|| Error::InvalidCurrencyCode(s.to_string()))
+    }
+}
+
+impl Display for CurrencyCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl serde::Serialize for CurrencyCode {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for CurrencyCode {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let code: std::borrow::Cow<str> = serde::Deserialize::deserialize(deserializer)?;
+
+        code.parse().map_err(|_| {
+            serde::de::Error::invalid_value(
+                serde::de::Unexpected::Str(&code),
+                &"an uppercase currency code",
+            )
+        })
+    }
+}
+
+/// A decimal amount with six fractional digits.
+///
+/// The string representation is a plain decimal number without trailing zeros (for example `4.99`).
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Amount {
+    micros: i64,
+}
+
+impl Amount {
+    pub const ZERO: Self = Self { micros: 0 };
+
+    #[must_use]
+    pub const fn from_micros(micros: i64) -> Self {
+        Self { micros }
+    }
+
+    #[must_use]
+    pub const fn micros(self) -> i64 {
+        self.micros
+    }
+
+    /// Convert a fraction, rounding to the nearest micro-unit.
+    ///
+    /// Returns `None` if the denominator is zero or the result would overflow.
+    #[must_use]
+    pub fn from_ratio(numerator: u64, denominator: u64) -> Option<Self> {
+        (denominator != 0)
+            .then(|| {
+                let numerator = i128::from(numerator) * i128::from(MICROS_PER_UNIT);
+                let denominator = i128::from(denominator);
+
+                (numerator + denominator / 2) / denominator
+            })
+            .and_then(|micros| i64::try_from(micros).ok())
+            .map(Self::from_micros)
+    }
+
+    #[must_use]
+    pub const fn is_zero(self) -> bool {
+        self.micros == 0
+    }
+
+    /// The amount in currency units as a floating-point number (for example for export).
+    #[must_use]
+    pub fn to_f64(self) -> f64 {
+        // The decimal representation is always a valid float, and parsing it avoids a lossy cast.
+        self.to_string().parse().unwrap_or_default()
+    }
+}
+
+impl FromStr for Amount {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::InvalidAmount(s.to_string());
+
+        let (negative, unsigned) = s.strip_prefix('-').map_or((false, s), |rest| (true, rest));
+        let (whole, fraction) = match unsigned.split_once('.') {
+            Some((whole, fraction)) if !fraction.is_empty() => (whole, fraction),
+            Some(_) => return Err(invalid()),
+            None => (unsigned, ""),
+        };
+
+        if whole.is_empty()
+            || fraction.len() > 6
+            || !whole.chars().all(|c| c.is_ascii_digit())
+            || !fraction.chars().all(|c| c.is_ascii_digit())
+        {
+            Err(invalid())
+        } else {
+            let whole = whole.parse::<i64>().map_err(|_| invalid())?;
+            let fraction = format!("{fraction:0<6}")
+                .parse::<i64>()
+                .map_err(|_| invalid())?;
+
+            let micros = whole
+                .checked_mul(MICROS_PER_UNIT)
+                .and_then(|micros| micros.checked_add(fraction))
+                .ok_or_else(invalid)?;
+
+            Ok(Self::from_micros(if negative { -micros } else { micros }))
+        }
+    }
+}
+
+impl Display for Amount {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let sign = if self.micros < 0 { "-" } else { "" };
+        let micros = self.micros.unsigned_abs();
+        let unit = MICROS_PER_UNIT.unsigned_abs();
+        let whole = micros / unit;
+        let fraction = micros % unit;
+
+        if fraction == 0 {
+            write!(f, "{sign}{whole}")
+        } else {
+            let fraction = format!("{fraction:06}");
+
+            write!(f, "{sign}{whole}.{}", fraction.trim_end_matches('0'))
+        }
+    }
+}
+
+impl serde::Serialize for Amount {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// A price in a specific currency.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, serde::Serialize)]
+pub struct Money {
+    pub amount: Amount,
+    pub currency: CurrencyCode,
+}
+
+impl Money {
+    #[must_use]
+    pub const fn new(amount: Amount, currency: CurrencyCode) -> Self {
+        Self { amount, currency }
+    }
+
+    #[must_use]
+    pub const fn is_free(&self) -> bool {
+        self.amount.is_zero()
+    }
+
+    /// Convert to the base currency of the given rates, if a rate is available.
+    #[must_use]
+    pub fn convert(&self, rates: &Rates) -> Option<Self> {
+        rates.convert(self)
+    }
+}
+
+impl Display for Money {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.amount, self.currency)
+    }
+}
+
+/// Exchange rates relative to a base currency.
+///
+/// Each rate is the number of units of the currency that one unit of the base currency buys. Rates
+/// are read from a local JSON file in the common `{"base": "USD", "rates": {"EUR": 0.92}}` form.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Rates {
+    base: CurrencyCode,
+    rates: HashMap<CurrencyCode, Amount>,
+}
+
+#[derive(serde::Deserialize)]
+struct RatesFile {
+    base: CurrencyCode,
+    rates: HashMap<CurrencyCode, serde_json::Number>,
+}
+
+impl Rates {
+    #[must_use]
+    pub const fn new(base: CurrencyCode, rates: HashMap<CurrencyCode, Amount>) -> Self {
+        Self { base, rates }
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let file = std::fs::File::open(path)?;
+        let rates_file: RatesFile = serde_json::from_reader(std::io::BufReader::new(file))?;
+
+        let rates = rates_file
+            .rates
+            .into_iter()
+            .map(|(currency, rate)| Ok((currency, Self::parse_rate(&rate.to_string())?)))
+            .collect::<Result<HashMap<_, _>, Error>>()?;
+
+        match rates.get(&rates_file.base) {
+            Some(rate) if *rate != Amount::from_micros(MICROS_PER_UNIT) => {
+                Err(Error::InvalidBaseRate(rates_file.base))
+            }
+            _ => Ok(Self::new(rates_file.base, rates)),
+        }
+    }
+
+    /// Rates often have more than six fractional digits, so we round instead of failing.
+    fn parse_rate(value: &str) -> Result<Amount, Error> {
+        let value = Self::expand_exponent(value)?;
+
+        match value.split_once('.') {
+            Some((whole, fraction)) if fraction.len() > 6 => {
+                let amount = format!("{whole}.{}", &fraction[..6]).parse::<Amount>()?;
+
+                if fraction.as_bytes()[6] >= b'5' {
+                    Ok(Amount::from_micros(amount.micros + 1))
+                } else {
+                    Ok(amount)
+                }
+            }
+            _ => value.parse(),
+        }
+    }
+
+    /// Rewrite exponent notation (which `serde_json` uses for very small or large numbers, for
+    /// example `1e-5`) as a plain decimal number.
+    fn expand_exponent(value: &str) -> Result<std::borrow::Cow<'_, str>, Error> {
+        // Anything outside this range would be rounded to zero or overflow an amount anyway.
+        const MAX_EXPONENT: i64 = 32;
+
+        let invalid = || Error::InvalidAmount(value.to_string());
+
+        let Some((mantissa, exponent)) = value.split_once(['e', 'E']) else {
+            return Ok(value.into());
+        };
+
+        let exponent = exponent
+            .parse::<i64>()
+            .ok()
+            .filter(|exponent| exponent.abs() <= MAX_EXPONENT)
+            .ok_or_else(invalid)?;
+        let (sign, mantissa) = mantissa
+            .strip_prefix('-')
+            .map_or(("", mantissa), |rest| ("-", rest));
+        let (whole, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+        let digits = format!("{whole}{fraction}");
+
+        if whole.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+            return Err(invalid());
+        }
+
+        // The exponent bound keeps every length here well within range.
+        let whole_len = i64::try_from(whole.len()).map_err(|_| invalid())?;
+        let digits_len = i64::try_from(digits.len()).map_err(|_| invalid())?;
+        let point = whole_len + exponent;
+        let zeros = |count: i64| "0".repeat(usize::try_from(count).unwrap_or_default());
+
+        Ok(if point >= digits_len {
+            format!("{sign}{digits}{}", zeros(point - digits_len))
+        } else if point > 0 {
+            let (whole, fraction) = digits.split_at(usize::try_from(point).unwrap_or_default());
+
+            format!("{sign}{whole}.{fraction}")
+        } else {
+            format!("{sign}0.{}{digits}", zeros(-point))
+        }
+        .into())
+    }
+
+    #[must_use]
+    pub const fn base(&self) -> CurrencyCode {
+        self.base
+    }
+
+    #[must_use]
+    pub fn convert(&self, money: &Money) -> Option<Money> {
+        if money.currency == self.base {
+            Some(*money)
+        } else {
+            self.rates
+                .get(&money.currency)
+                .filter(|rate| rate.micros > 0)
+                .and_then(|rate| {
+                    let numerator = i128::from(money.amount.micros) * i128::from(MICROS_PER_UNIT);
+                    let denominator = i128::from(rate.micros);
+
+                    i64::try_from((numerator + denominator / 2) / denominator).ok()
+                })
+                .map(|micros| Money::new(Amount::from_micros(micros), self.base))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn amount_round_trip() {
+        for value in ["0", "4.99", "-1.5", "120", "0.000001"] {
+            assert_eq!(value.parse::<Amount>().unwrap().to_string(), value);
+        }
+
+        assert_eq!("4.990".parse::<Amount>().unwrap().micros(), 4_990_000);
+        assert!("4.".parse::<Amount>().is_err());
+        assert!("1.0000001".parse::<Amount>().is_err());
+        assert_eq!(Amount::from_ratio(499, 100), Some("4.99".parse().unwrap()));
+    }
+
+    #[test]
+    fn convert_to_base() {
+        let usd = "USD".parse::<CurrencyCode>().unwrap();
+        let eur = "EUR".parse::<CurrencyCode>().unwrap();
+        let rates = Rates::new(usd, HashMap::from([(eur, "0.8".parse().unwrap())]));
+
+        let price = Money::new("4".parse().unwrap(), eur);
+
+        assert_eq!(
+            price.convert(&rates),
+            Some(Money::new("5".parse().unwrap(), usd))
+        );
+        assert_eq!(
+            Money::new(Amount::ZERO, "JPY".parse().unwrap()).convert(&rates),
+            None
+        );
+        assert!("usd".parse::<CurrencyCode>().is_err());
+        assert_eq!(
+            Rates::parse_rate("0.9234567").unwrap(),
+            "0.923457".parse().unwrap()
+        );
+    }
+
+    #[test]
+    fn parse_exponent_rates() {
+        assert_eq!(Rates::parse_rate("1e-5").unwrap(), Amount::from_micros(10));
+        assert_eq!(Rates::parse_rate("1.5E-7").unwrap(), Amount::from_micros(0));
+        assert_eq!(Rates::parse_rate("2.5e-6").unwrap(), Amount::from_micros(3));
+        assert_eq!(
+            Rates::parse_rate("1.25e3").unwrap(),
+            "1250".parse().unwrap()
+        );
+        assert_eq!(
+            Rates::parse_rate(
+                &serde_json::Number::from_f64(0.000_012_5)
+                    .unwrap()
+                    .to_string()
+            )
+            .unwrap(),
+            Amount::from_micros(13)
+        );
+        assert!(Rates::parse_rate("1e-999").is_err());
+        assert!(Rates::parse_rate("e5").is_err());
+    }
+}
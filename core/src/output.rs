@@ -0,0 +1,211 @@
+//! Output formats for the command-line tools.
+
+use std::fmt::Display;
+use std::io::Write;
+use std::str::FromStr;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("CSV error")]
+    Csv(#[from] csv::Error),
+    #[error("JSON error")]
+    Json(#[from] serde_json::Error),
+    #[error("I/O error")]
+    Io(#[from] std::io::Error),
+    #[error("Invalid output format")]
+    InvalidFormat(String),
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Format {
+    /// Comma-separated values without a header row.
+    #[default]
+    Csv,
+    /// Comma-separated values with a header row (taken from the field names of the first record).
+    CsvWithHeaders,
+    /// One JSON object per line, with the same field names as the CSV headers.
+    Jsonl,
+}
+
+impl Format {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::CsvWithHeaders => "csv-with-headers",
+            Self::Jsonl => "jsonl",
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(Self::Csv),
+            "csv-with-headers" => Ok(Self::CsvWithHeaders),
+            "jsonl" => Ok(Self::Jsonl),
+            other => Err(Error::InvalidFormat(other.to_string())),
+        }
+    }
+}
+
+impl Display for Format {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Writes serializable records in the selected format.
+///
+/// Note that in the CSV formats records must be flat (no nested structures or sequences).
+pub enum Writer<W: Write> {
+    Csv {
+        writer: Box<csv::Writer<W>>,
+        headers: bool,
+        wrote_headers: bool,
+    },
+    Jsonl(W),
+}
+
+impl<W: Write> Writer<W> {
+    #[must_use]
+    pub fn new(format: Format, writer: W) -> Self {
+        match format {
+            Format::Csv | Format::CsvWithHeaders => {
+                let headers = format == Format::CsvWithHeaders;
+
+                Self::Csv {
+                    writer: Box::new(
+                        csv::WriterBuilder::new()
+                            .has_headers(headers)
+                            .from_writer(writer),
+                    ),
+                    headers,
+                    wrote_headers: false,
+                }
+            }
+            Format::Jsonl => Self::Jsonl(writer),
+        }
+    }
+
+    pub fn write<T: serde::Serialize>(&mut self, record: &T) -> Result<(), Error> {
+        match self {
+            Self::Csv { writer, .. } => Ok(writer.serialize(record)?),
+            Self::Jsonl(writer) => {
+                serde_json::to_writer(&mut *writer, record)?;
+                Ok(writeln!(writer)?)
+            }
+        }
+    }
+
+    /// Write a row with dynamic columns (for example the result of a SQL query).
+    ///
+    /// The column names are only used as a header row (or as JSON field names).
+    pub fn write_row<C: AsRef<str>, V: AsRef<str>>(
+        &mut self,
+        columns: &[C],
+        row: &[V],
+    ) -> Result<(), Error> {
+        match self {
+            Self::Csv {
+                writer,
+                headers,
+                wrote_headers,
+            } => {
+                if *headers && !*wrote_headers {
+                    writer.write_record(columns.iter().map(AsRef::as_ref))?;
+                    *wrote_headers = true;
+                }
+
+                Ok(writer.write_record(row.iter().map(AsRef::as_ref))?)
+            }
+            Self::Jsonl(writer) => {
+                let object = columns
+                    .iter()
+                    .zip(row)
+                    .map(|(column, value)| {
+                        (
+                            column.as_ref().to_string(),
+                            serde_json::Value::from(value.as_ref()),
+                        )
+                    })
+                    .collect::<serde_json::Map<_, _>>();
+
+                serde_json::to_writer(&mut *writer, &object)?;
+                Ok(writeln!(writer)?)
+            }
+        }
+    }
+
+    pub fn flush(&mut self) -> Result<(), Error> {
+        match self {
+            Self::Csv { writer, .. } => Ok(writer.flush()?),
+            Self::Jsonl(writer) => Ok(writer.flush()?),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(serde::Serialize)]
+    struct Record<'a> {
+        id: u64,
+        name: &'a str,
+        rating: Option<f64>,
+    }
+
+    const RECORDS: [Record<'static>; 2] = [
+        Record {
+            id: 1,
+            name: "First, with comma",
+            rating: Some(4.5),
+        },
+        Record {
+            id: 2,
+            name: "Second",
+            rating: None,
+        },
+    ];
+
+    fn write_records(format: Format) -> String {
+        let mut writer = Writer::new(format, vec![]);
+
+        for record in &RECORDS {
+            writer.write(record).unwrap();
+        }
+
+        writer.flush().unwrap();
+
+        let output = match writer {
+            Writer::Csv { writer, .. } => writer.into_inner().unwrap(),
+            Writer::Jsonl(writer) => writer,
+        };
+
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn write_csv() {
+        assert_eq!(
+            write_records(Format::Csv),
+            "1,\"First, with comma\",4.5\n2,Second,\n"
+        );
+        assert_eq!(
+            write_records(Format::CsvWithHeaders),
+            "id,name,rating\n1,\"First, with comma\",4.5\n2,Second,\n"
+        );
+    }
+
+    #[test]
+    fn write_jsonl() {
+        assert_eq!(
+            write_records(Format::Jsonl),
+            "{\"id\":1,\"name\":\"First, with comma\",\"rating\":4.5}\n\
+             {\"id\":2,\"name\":\"Second\",\"rating\":null}\n"
+        );
+    }
+}
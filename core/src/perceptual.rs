@@ -0,0 +1,320 @@
+//! Perceptual hashes of icons, for finding near-duplicate images within and across stores.
+//!
+//! Two 64-bit hashes are computed for each image: a difference hash (dHash), which compares
+//! neighbouring pixels of a 9x8 grayscale thumbnail, and a DCT hash (pHash), which compares the
+//! lowest 8x8 frequencies of a 32x32 grayscale thumbnail with their median. Similar images have
+//! hashes that differ in few bits, even after resizing, recompression, or small edits.
+use crate::{
+    index::Store,
+    media::{Entry, MediaStore},
+    model::ImageType,
+};
+use chrono::{DateTime, Utc};
+use image::{DynamicImage, imageops::FilterType};
+use std::collections::{BTreeMap, HashMap};
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+
+const CACHE_FILE: &str = "perceptual.jsonl";
+const PHASH_SIZE: u32 = 32;
+const PHASH_LOW: u32 = 8;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("I/O error")]
+    Io(#[from] std::io::Error),
+    #[error("JSON error")]
+    Json(#[from] serde_json::Error),
+    #[error("Image error")]
+    Image(#[from] image::ImageError),
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct Hashes {
+    pub dhash: u64,
+    pub phash: u64,
+}
+
+impl Hashes {
+    #[must_use]
+    pub fn from_image(image: &DynamicImage) -> Self {
+        Self {
+            dhash: dhash(image),
+            phash: phash(image),
+        }
+    }
+
+    pub fn from_bytes(contents: &[u8]) -> Result<Self, Error> {
+        Ok(Self::from_image(&image::load_from_memory(contents)?))
+    }
+
+    #[must_use]
+    pub const fn distance(&self, other: &Self) -> Distance {
+        Distance {
+            dhash: (self.dhash ^ other.dhash).count_ones(),
+            phash: (self.phash ^ other.phash).count_ones(),
+        }
+    }
+}
+
+/// The number of bits that differ between two sets of hashes.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct Distance {
+    pub dhash: u32,
+    pub phash: u32,
+}
+
+fn dhash(image: &DynamicImage) -> u64 {
+    let thumbnail = image.resize_exact(9, 8, FilterType::Triangle).to_luma8();
+    let mut hash = 0;
+
+    for y in 0..8 {
+        for x in 0..8 {
+            hash = (hash << 1)
+                | u64::from(thumbnail.get_pixel(x, y).0[0] > thumbnail.get_pixel(x + 1, y).0[0]);
+        }
+    }
+
+    hash
+}
+
+fn phash(image: &DynamicImage) -> u64 {
+    let thumbnail = image
+        .resize_exact(PHASH_SIZE, PHASH_SIZE, FilterType::Triangle)
+        .to_luma8();
+
+    // Unnormalized DCT-II basis: `cosines[k][n]` is `cos(π (2n + 1) k / 2N)`.
+    let cosines = (0..PHASH_LOW)
+        .map(|k| {
+            (0..PHASH_SIZE)
+                .map(|n| {
+                    (std::f64::consts::PI * f64::from(2 * n + 1) * f64::from(k)
+                        / f64::from(2 * PHASH_SIZE))
+                    .cos()
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    // Transform the rows, keeping only the low frequencies, and then the columns.
+    let rows = (0..PHASH_SIZE)
+        .map(|y| {
+            cosines
+                .iter()
+                .map(|basis| {
+                    (0..PHASH_SIZE)
+                        .zip(basis)
+                        .map(|(x, cosine)| f64::from(thumbnail.get_pixel(x, y).0[0]) * cosine)
+                        .sum::<f64>()
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let coefficients = cosines
+        .iter()
+        .flat_map(|basis| {
+            let rows = &rows;
+
+            (0..cosines.len()).map(move |u| {
+                rows.iter()
+                    .zip(basis)
+                    .map(|(row, cosine)| row[u] * cosine)
+                    .sum::<f64>()
+            })
+        })
+        .collect::<Vec<_>>();
+
+    // The first coefficient is the average brightness, which is left out of the median.
+    let mut sorted = coefficients[1..].to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let median = sorted[sorted.len() / 2];
+
+    coefficients.iter().fold(0, |hash, coefficient| {
+        (hash << 1) | u64::from(*coefficient > median)
+    })
+}
+
+/// Hashes computed so far, keyed by the content hash of the stored file.
+///
+/// The cache is saved in the media store directory (one JSON object per line).
+pub struct HashCache {
+    path: PathBuf,
+    hashes: HashMap<String, Hashes>,
+}
+
+#[derive(serde::Deserialize, serde::Serialize)]
+struct CacheLine {
+    hash: String,
+    #[serde(flatten)]
+    hashes: Hashes,
+}
+
+impl HashCache {
+    pub fn open(media: &MediaStore) -> Result<Self, Error> {
+        let path = media.root().join(CACHE_FILE);
+        let mut hashes = HashMap::new();
+
+        match std::fs::File::open(&path) {
+            Ok(file) => {
+                for line in std::io::BufReader::new(file).lines() {
+                    let line = line?;
+
+                    if !line.trim().is_empty() {
+                        let line = serde_json::from_str::<CacheLine>(&line)?;
+                        hashes.insert(line.hash, line.hashes);
+                    }
+                }
+            }
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {}
+            Err(error) => return Err(error.into()),
+        }
+
+        Ok(Self { path, hashes })
+    }
+
+    #[must_use]
+    pub fn get(&self, hash: &str) -> Option<Hashes> {
+        self.hashes.get(hash).copied()
+    }
+
+    /// Look up the hashes for a stored file, computing and saving them if necessary.
+    pub fn compute<P: AsRef<Path>>(&mut self, hash: &str, path: P) -> Result<Hashes, Error> {
+        if let Some(hashes) = self.get(hash) {
+            Ok(hashes)
+        } else {
+            let hashes = Hashes::from_bytes(&std::fs::read(path)?)?;
+
+            let file = std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&self.path)?;
+            let mut writer = std::io::BufWriter::new(file);
+
+            serde_json::to_writer(
+                &mut writer,
+                &CacheLine {
+                    hash: hash.to_string(),
+                    hashes,
+                },
+            )?;
+            writeln!(writer)?;
+            writer.flush()?;
+
+            self.hashes.insert(hash.to_string(), hashes);
+
+            Ok(hashes)
+        }
+    }
+}
+
+/// The most recently recorded icon for each app in the manifest.
+#[must_use]
+pub fn latest_icons(entries: &[Entry]) -> Vec<&Entry> {
+    let mut latest = BTreeMap::<(Store, &str), &Entry>::new();
+
+    for entry in entries
+        .iter()
+        .filter(|entry| entry.image_type == ImageType::Icon && entry.position == 0)
+    {
+        latest
+            .entry((entry.store, entry.app_id.as_str()))
+            .and_modify(|current| {
+                if entry.timestamp > current.timestamp {
+                    *current = entry;
+                }
+            })
+            .or_insert(entry);
+    }
+
+    latest.into_values().collect()
+}
+
+/// The perceptual hashes of an app's icon.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Icon {
+    pub store: Store,
+    pub app_id: String,
+    pub timestamp: DateTime<Utc>,
+    /// The content hash of the stored file.
+    pub hash: String,
+    pub hashes: Hashes,
+}
+
+impl Icon {
+    #[must_use]
+    pub fn new(entry: &Entry, hashes: Hashes) -> Self {
+        Self {
+            store: entry.store,
+            app_id: entry.app_id.clone(),
+            timestamp: entry.timestamp,
+            hash: entry.object.hash.clone(),
+            hashes,
+        }
+    }
+}
+
+/// A pair of icons whose pHash distance is at most the threshold.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Match<'a> {
+    pub left: &'a Icon,
+    pub right: &'a Icon,
+    pub distance: Distance,
+}
+
+/// Find pairs of near-duplicate icons, closest first.
+///
+/// This compares every pair of icons, which is fine for tens of thousands of apps.
+#[must_use]
+pub fn similar(icons: &[Icon], max_distance: u32, cross_store: bool) -> Vec<Match<'_>> {
+    let mut matches = icons
+        .iter()
+        .enumerate()
+        .flat_map(|(index, left)| {
+            icons[index + 1..].iter().filter_map(move |right| {
+                let distance = left.hashes.distance(&right.hashes);
+
+                (distance.phash <= max_distance && (!cross_store || left.store != right.store))
+                    .then_some(Match {
+                        left,
+                        right,
+                        distance,
+                    })
+            })
+        })
+        .collect::<Vec<_>>();
+
+    matches.sort_by_key(|value| value.distance);
+    matches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{GrayImage, Luma};
+
+    /// A 4x4 grid of blocks with distinct brightness values.
+    fn pattern(size: u32, invert: bool) -> DynamicImage {
+        const VALUES: [u8; 16] = [
+            40, 200, 90, 160, 20, 230, 120, 70, 180, 10, 140, 250, 60, 100, 220, 30,
+        ];
+
+        DynamicImage::ImageLuma8(GrayImage::from_fn(size, size, |x, y| {
+            let index = usize::try_from((y * 4 / size) * 4 + x * 4 / size).unwrap();
+            let value = VALUES[index];
+
+            Luma([if invert { 255 - value } else { value }])
+        }))
+    }
+
+    #[test]
+    fn hashes_survive_resizing() {
+        let original = Hashes::from_image(&pattern(256, false));
+        let resized = Hashes::from_image(&pattern(128, false));
+        let inverted = Hashes::from_image(&pattern(256, true));
+
+        assert!(original.distance(&resized).phash <= 4);
+        assert!(original.distance(&resized).dhash <= 4);
+        assert!(original.distance(&inverted).phash > 32);
+    }
+}
@@ -0,0 +1,169 @@
+//! Keyword rank tracking.
+//!
+//! A [`Run`] is the ordered list of app IDs returned by a single search for a keyword in one store
+//! and country. Given a set of tracked app IDs, each run is reduced to one [`Observation`] per app,
+//! and a [`History`] collects these observations into a time series keyed by run timestamp.
+use crate::{country::Country, index::Store};
+use chrono::{DateTime, Utc};
+use std::collections::{BTreeMap, HashMap};
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Run {
+    pub timestamp: DateTime<Utc>,
+    pub store: Store,
+    pub country: Country,
+    pub keyword: String,
+    /// App IDs in result order (Apple IDs are formatted as decimal strings).
+    pub app_ids: Vec<String>,
+}
+
+impl Run {
+    /// The one-indexed rank of the app, if it appears in the results.
+    #[must_use]
+    pub fn rank(&self, app_id: &str) -> Option<usize> {
+        self.app_ids
+            .iter()
+            .position(|id| id == app_id)
+            .map(|index| index + 1)
+    }
+
+    pub fn observations<'a, S: AsRef<str>>(
+        &'a self,
+        tracked: &'a [S],
+    ) -> impl Iterator<Item = Observation> + 'a {
+        tracked.iter().map(|app_id| Observation {
+            timestamp: self.timestamp,
+            store: self.store,
+            country: self.country,
+            keyword: self.keyword.clone(),
+            app_id: app_id.as_ref().to_string(),
+            rank: self.rank(app_id.as_ref()),
+            result_count: self.app_ids.len(),
+        })
+    }
+}
+
+/// The rank of a tracked app in a single run.
+///
+/// A missing rank means that the app did not appear in the results.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+pub struct Observation {
+    pub timestamp: DateTime<Utc>,
+    pub store: Store,
+    #[serde(with = "crate::country::country_code")]
+    pub country: Country,
+    pub keyword: String,
+    pub app_id: String,
+    pub rank: Option<usize>,
+    pub result_count: usize,
+}
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Series {
+    pub store: Store,
+    pub country: Country,
+    pub keyword: String,
+    pub app_id: String,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Point {
+    pub rank: Option<usize>,
+    pub result_count: usize,
+}
+
+/// Rank time series for tracked apps, with one point per run.
+#[derive(Clone, Debug, Default)]
+pub struct History {
+    series: HashMap<Series, BTreeMap<DateTime<Utc>, Point>>,
+}
+
+impl History {
+    pub fn add<S: AsRef<str>>(&mut self, run: &Run, tracked: &[S]) {
+        for observation in run.observations(tracked) {
+            self.series
+                .entry(Series {
+                    store: observation.store,
+                    country: observation.country,
+                    keyword: observation.keyword,
+                    app_id: observation.app_id,
+                })
+                .or_default()
+                .insert(
+                    observation.timestamp,
+                    Point {
+                        rank: observation.rank,
+                        result_count: observation.result_count,
+                    },
+                );
+        }
+    }
+
+    #[must_use]
+    pub fn get(&self, series: &Series) -> Option<&BTreeMap<DateTime<Utc>, Point>> {
+        self.series.get(series)
+    }
+
+    /// All observations, grouped by series and ordered by time within each series.
+    #[must_use]
+    pub fn observations(&self) -> Vec<Observation> {
+        let mut series = self.series.iter().collect::<Vec<_>>();
+        series.sort_by_cached_key(|(series, _)| {
+            (
+                series.store,
+                series.country.to_string(),
+                series.keyword.clone(),
+                series.app_id.clone(),
+            )
+        });
+
+        series
+            .into_iter()
+            .flat_map(|(series, points)| {
+                points.iter().map(|(timestamp, point)| Observation {
+                    timestamp: *timestamp,
+                    store: series.store,
+                    country: series.country,
+                    keyword: series.keyword.clone(),
+                    app_id: series.app_id.clone(),
+                    rank: point.rank,
+                    result_count: point.result_count,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(timestamp_s: i64, app_ids: &[&str]) -> Run {
+        Run {
+            timestamp: DateTime::from_timestamp(timestamp_s, 0).unwrap(),
+            store: Store::Google,
+            country: Country::UnitedStates,
+            keyword: "chess".to_string(),
+            app_ids: app_ids.iter().map(|id| (*id).to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn rank_history() {
+        let tracked = ["com.chess", "org.lichess.mobileapp"];
+        let mut history = History::default();
+
+        history.add(&run(200, &["org.lichess.mobileapp", "com.chess"]), &tracked);
+        history.add(&run(100, &["com.chess", "com.other"]), &tracked);
+
+        let observations = history.observations();
+
+        assert_eq!(observations.len(), 4);
+        assert_eq!(observations[0].app_id, "com.chess");
+        assert_eq!(observations[0].rank, Some(1));
+        assert_eq!(observations[1].rank, Some(2));
+        assert_eq!(observations[2].app_id, "org.lichess.mobileapp");
+        assert_eq!(observations[2].rank, None);
+        assert_eq!(observations[3].rank, Some(1));
+    }
+}
@@ -11,7 +11,9 @@ pub enum Data {
     DeveloperInitial(crate::model::developer::Page),
     DeveloperPagination(DeveloperId, crate::model::developer::Page),
     Search(crate::model::search::Page),
+    Category(crate::model::search::Page),
     Reviews(crate::model::review::Page),
+    Suggestions(Vec<String>),
 }
 
 impl bounded_static::IntoBoundedStatic for Data {
@@ -78,6 +80,14 @@ impl Archiveable for Data {
                     (field, response.map(|data| Self::Search(data.0.into())))
                 }))
             }
+            RequestData::Category { .. } => {
+                let next =
+                    map.next_entry::<Field, Response<(crate::model::category::CategoryResult,)>>()?;
+
+                Ok(next.map(|(field, response)| {
+                    (field, response.map(|data| Self::Category(data.0.into())))
+                }))
+            }
             RequestData::Reviews { .. } => {
                 let next =
                     map.next_entry::<Field, Response<crate::model::review::PageResponse>>()?;
@@ -112,6 +122,20 @@ impl Archiveable for Data {
                 })
                 .map_or(Ok(None), |value| value.map(Some))
             }
+            RequestData::CategoryPagination { .. } => {
+                let next = map
+                    .next_entry::<Field, Response<crate::model::search::SearchPaginationResult>>(
+                    )?;
+
+                Ok(next.map(|(field, response)| {
+                    (field, response.map(|data| Self::Category(data.into())))
+                }))
+            }
+            RequestData::Suggest { .. } => {
+                let next = map.next_entry::<Field, Response<Vec<String>>>()?;
+
+                Ok(next.map(|(field, response)| (field, response.map(Self::Suggestions))))
+            }
         }
     }
 }
@@ -147,3 +171,50 @@ fn paginated_developer_page_exchange<
         },
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Data;
+    use crate::request::{Request, RequestData, params::category::Collection};
+    use app_store_access::{country::Country, language::Language};
+    use scraper_trail::{archive::entry::Entry, request::params::Params};
+
+    #[test]
+    fn deserialize_category_pagination() {
+        let params = Request::category_pagination(
+            "GAME_PUZZLE",
+            Some(Collection::TopFree),
+            100,
+            "next-page",
+            Language::English,
+            Country::UnitedStates,
+        );
+        let request = params.build_request(None);
+
+        let entry = serde_json::json!({
+            "request": request,
+            "response": {
+                "headers": {},
+                "data": [[[[], null, null, null, null, null, null, [null, "last-page"]]]]
+            }
+        })
+        .to_string();
+
+        let entry = serde_json::from_str::<Entry<'_, Data>>(&entry).unwrap();
+
+        assert_eq!(
+            entry.request_params.data,
+            RequestData::CategoryPagination {
+                code: "GAME_PUZZLE".into(),
+                collection: Some(Collection::TopFree),
+                number: 100,
+                token: "next-page".into(),
+            }
+        );
+
+        match entry.exchange.response.data {
+            Data::Category(page) => assert_eq!(page.token.as_deref(), Some("last-page")),
+            other => panic!("expected a category page, got {other:?}"),
+        }
+    }
+}
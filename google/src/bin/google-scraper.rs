@@ -3,7 +3,7 @@ use app_store_access_google::{
     archive::Data,
     request::{
         RequestData,
-        params::{developer::DeveloperId, review::SortOrder},
+        params::{category::Collection, developer::DeveloperId, review::SortOrder},
     },
 };
 use cli_helpers::prelude::*;
@@ -20,14 +20,22 @@ pub enum Error {
     Client(#[from] app_store_access_google::client::Error),
     #[error("Client suggestion error")]
     SuggestClient(#[from] app_store_access_google::client::suggest::Error),
-    #[error("CSV error")]
-    Csv(#[from] csv::Error),
+    #[error("Output error")]
+    Output(#[from] app_store_access::output::Error),
     #[error("Google parsing error")]
     GoogleParse(#[from] app_store_access_google::parse::Error),
     #[error("JavaScript error")]
     Js(#[from] app_store_access::js::Error),
     #[error("JSON file error")]
     JsonFile(PathBuf, serde_json::Error),
+    #[error("Index error")]
+    Index(#[from] app_store_access::index::Error),
+    #[error("Index build error")]
+    IndexBuild(#[from] app_store_access_google::index::Error),
+    #[error("Export error")]
+    Export(#[from] app_store_access_google::export::Error),
+    #[error("Money error")]
+    Money(#[from] app_store_access::money::Error),
 }
 
 impl Error {
@@ -57,22 +65,44 @@ async fn main() -> Result<(), Error> {
             let client =
                 app_store_access_google::client::Client::new(Some(&archive), Default::default())?;
 
-            let mut writer = csv::WriterBuilder::new()
-                .has_headers(false)
-                .from_writer(std::io::stdout());
+            let mut writer = app_store_access::output::Writer::new(opts.format, std::io::stdout());
 
             match command {
-                ApiCommand::App { id } => {
+                ApiCommand::App {
+                    id,
+                    all_countries: true,
+                    rates,
+                    delay,
+                } => {
+                    let rates = rates
+                        .map(app_store_access::money::Rates::load)
+                        .transpose()?;
+
+                    for country in app_store_access_google::request::markets::countries() {
+                        let app = client.app(&id, lang, country).await?;
+
+                        writer.write(&AvailabilityRecord::new(
+                            &id,
+                            country,
+                            app.as_ref(),
+                            rates.as_ref(),
+                        ))?;
+                        writer.flush()?;
+
+                        tokio::time::sleep(Duration::from_millis(delay)).await;
+                    }
+                }
+                ApiCommand::App { id, .. } => {
                     let app = client.app(&id, lang, country).await?;
 
                     match app {
                         Some(app) => {
-                            writer.write_record([
-                                app.app_id.clone(),
-                                app.developer.id.to_string(),
-                                app.title,
-                                app.developer.name,
-                            ])?;
+                            writer.write(&AppRecord {
+                                app_id: &app.app_id,
+                                developer_id: app.developer.id.to_string(),
+                                title: &app.title,
+                                developer_name: &app.developer.name,
+                            })?;
                         }
                         None => {
                             ::log::warn!("App not found: {}", id);
@@ -98,14 +128,11 @@ async fn main() -> Result<(), Error> {
                     }
 
                     for app in results {
-                        writer.write_record([
-                            app.id.clone(),
-                            app.developer_id()
-                                .map(|developer_id| developer_id.to_string())
-                                .unwrap_or_default(),
-                            app.title,
-                            app.developer.name,
-                        ])?;
+                        if !price_filter.matches(&app) {
+                            ::log::warn!("Search result does not match price filter: {}", app.id);
+                        }
+
+                        writer.write(&SearchResultRecord::new(&app))?;
 
                         if full {
                             client.app(&app.id, lang, country).await?;
@@ -114,13 +141,49 @@ async fn main() -> Result<(), Error> {
                         }
                     }
                 }
-                ApiCommand::Developer { id } => {
-                    let apps = client.developer(&id, lang, country, 100).await?;
+                ApiCommand::Category {
+                    code,
+                    collection,
+                    full,
+                    delay,
+                } => {
+                    let results = client
+                        .category(&code, collection, lang, country, 100)
+                        .await?;
 
-                    match apps {
-                        Some(apps) => {
-                            for app in apps {
-                                writer.write_record([app.app_id, app.developer_id.to_string()])?;
+                    if full {
+                        ::log::info!("Downloading full information for {} apps", results.len());
+                    }
+
+                    for app in results {
+                        writer.write(&SearchResultRecord::new(&app))?;
+
+                        if full {
+                            client.app(&app.id, lang, country).await?;
+                            writer.flush()?;
+                            tokio::time::sleep(Duration::from_millis(delay)).await;
+                        }
+                    }
+                }
+                ApiCommand::Developer { id, hydrate } => {
+                    let portfolio = client.developer(&id, lang, country, 100, hydrate).await?;
+
+                    match portfolio {
+                        Some(portfolio) => {
+                            for app in &portfolio.apps {
+                                let details = portfolio.details.as_ref().and_then(|details| {
+                                    details.iter().find(|details| details.app_id == app.app_id)
+                                });
+
+                                writer.write(&DeveloperAppRecord {
+                                    app_id: &app.app_id,
+                                    developer_id: app.developer_id.to_string(),
+                                    developer_name: &portfolio.profile.name,
+                                    developer_website: portfolio.profile.website.as_deref(),
+                                    featured: portfolio.profile.featured_app_id.as_deref()
+                                        == Some(app.app_id.as_str()),
+                                    title: details.map(|details| details.title.as_str()),
+                                })?;
                             }
                         }
                         None => {
@@ -134,21 +197,21 @@ async fn main() -> Result<(), Error> {
                         .await?;
 
                     for review in reviews {
-                        writer.write_record([
-                            review.id.to_string(),
-                            review.user.id.map(|id| id.to_string()).unwrap_or_default(),
-                            review.user.display_name,
-                        ])?;
+                        writer.write(&ReviewRecord {
+                            id: review.id,
+                            user_id: review.user.id.map(|id| id.to_string()),
+                            user_name: &review.user.display_name,
+                        })?;
                     }
                 }
                 ApiCommand::Suggest { query } => {
                     for suggestion in client.lookup_suggestions(&query, country, lang).await? {
-                        writer.write_record([
-                            query.to_string(),
-                            lang.to_string(),
-                            country.to_string(),
+                        writer.write(&SuggestionRecord {
+                            query: &query,
+                            lang,
+                            country,
                             suggestion,
-                        ])?;
+                        })?;
                     }
                 }
             }
@@ -160,11 +223,9 @@ async fn main() -> Result<(), Error> {
             most_recent_first,
             command,
         } => {
-            let store = scraper_trail::archive::store::Store::new(archive);
+            let store = scraper_trail::archive::store::Store::new(archive.clone());
 
-            let mut writer = csv::WriterBuilder::new()
-                .has_headers(false)
-                .from_writer(std::io::stdout());
+            let mut writer = app_store_access::output::Writer::new(opts.format, std::io::stdout());
 
             match command {
                 StoreCommand::Apps => {
@@ -173,7 +234,7 @@ async fn main() -> Result<(), Error> {
                             entry.map_err(|error| Error::from_scraper_store_error(path, error))?;
 
                         if let Data::Detail(app) = entry.exchange.response.data {
-                            writer.write_record([app.app_id, app.developer.id.to_string()])?;
+                            writer.write(&StoreAppRecord::new(&app))?;
                         }
                     }
                 }
@@ -196,25 +257,105 @@ async fn main() -> Result<(), Error> {
                             };
 
                             for app in page.apps.unwrap_or_default() {
-                                writer.write_record([
-                                    request
-                                        .map(|(query, _)| query.to_string())
-                                        .unwrap_or_default(),
-                                    request
+                                writer.write(&StoreSearchRecord {
+                                    query: request.map(|(query, _)| query.as_ref()),
+                                    price: request
                                         .and_then(|(_, price)| price)
-                                        .map(|price| price.to_string())
-                                        .unwrap_or_default(),
-                                    app.id,
-                                    app.developer.id,
-                                    request_token
-                                        .map(|token| token.to_string())
-                                        .unwrap_or_default(),
-                                    page.token.clone().unwrap_or_default(),
-                                ])?;
+                                        .map(|price| price.to_string()),
+                                    app_id: &app.id,
+                                    developer_id: &app.developer.id,
+                                    request_token: request_token.map(AsRef::as_ref),
+                                    token: page.token.as_deref(),
+                                })?;
+                            }
+                        }
+                    }
+                }
+                StoreCommand::Suggestions => {
+                    for (path, entry) in store.entries::<Data>(most_recent_first)? {
+                        let entry =
+                            entry.map_err(|error| Error::from_scraper_store_error(path, error))?;
+
+                        if let (Data::Suggestions(suggestions), RequestData::Suggest { query }) =
+                            (entry.exchange.response.data, &entry.request_params.data)
+                        {
+                            for suggestion in suggestions {
+                                writer.write(&SuggestionRecord {
+                                    query,
+                                    lang: entry.request_params.language,
+                                    country: entry.request_params.country,
+                                    suggestion,
+                                })?;
                             }
                         }
                     }
                 }
+                StoreCommand::ReviewHistory => {
+                    let mut history =
+                        app_store_access_google::model::review::history::History::default();
+
+                    for (path, entry) in store.entries::<Data>(most_recent_first)? {
+                        let entry =
+                            entry.map_err(|error| Error::from_scraper_store_error(path, error))?;
+
+                        if let (Data::Reviews(page), RequestData::Reviews { app_id, .. }) =
+                            (&entry.exchange.response.data, &entry.request_params.data)
+                        {
+                            for review in &page.reviews {
+                                history.add(app_id, review);
+                            }
+                        }
+                    }
+
+                    for edit in history.edits() {
+                        writer.write(&ReviewEditRecord {
+                            review_id: edit.review_id,
+                            app_id: &edit.app_id,
+                            before_timestamp: edit.before.timestamp,
+                            after_timestamp: edit.after.timestamp,
+                            before_score: edit.before.score,
+                            after_score: edit.after.score,
+                            text_changed: edit.text_changed(),
+                        })?;
+                    }
+                }
+                StoreCommand::Index { command } => match command {
+                    IndexCommand::Build { index } => {
+                        let mut index = app_store_access::index::Index::open(index)?;
+                        let stats = app_store_access_google::index::build(&archive, &mut index)?;
+
+                        ::log::info!(
+                            "Indexed {} new files ({} already indexed, {} failed)",
+                            stats.ingested,
+                            stats.skipped,
+                            stats.failed
+                        );
+                    }
+                    IndexCommand::Query { index, sql } => {
+                        let index = app_store_access::index::Index::open(index)?;
+                        let (columns, rows) = index.query(&sql)?;
+
+                        for row in rows {
+                            writer.write_row(&columns, &row)?;
+                        }
+                    }
+                },
+                StoreCommand::Export { output } => {
+                    let stats = app_store_access_google::export::export(
+                        &archive,
+                        output,
+                        most_recent_first,
+                    )?;
+
+                    ::log::info!(
+                        "Exported {} apps, {} developers, {} reviews, {} search results ({} files failed)",
+                        stats.apps,
+                        stats.developers,
+                        stats.reviews,
+                        stats.search_results,
+                        stats.failed
+                    );
+                }
 
                 #[cfg(not(feature = "strict"))]
                 StoreCommand::FixStrict => {
@@ -248,7 +389,10 @@ async fn main() -> Result<(), Error> {
                     for fix in fixes {
                         match fix {
                             Fix::UnknownReviewCriterionType(value) => {
-                                writer.write_record(["review_criterion_type", &value])?;
+                                writer.write(&FixRecord {
+                                    kind: "review_criterion_type",
+                                    value: &value,
+                                })?;
                             }
                         }
                     }
@@ -267,6 +411,9 @@ async fn main() -> Result<(), Error> {
 struct Opts {
     #[clap(flatten)]
     verbose: Verbosity,
+    /// Output format: csv, csv-with-headers, or jsonl
+    #[clap(long, global = true, default_value = "csv")]
+    format: app_store_access::output::Format,
     #[clap(subcommand)]
     command: Command,
 }
@@ -301,6 +448,17 @@ enum ApiCommand {
     App {
         #[clap(long)]
         id: String,
+        /// Request the app in every known country and print an availability and price matrix
+        ///
+        /// Google Play has no separate lookup endpoint, so this is also the multi-country lookup.
+        #[clap(long)]
+        all_countries: bool,
+        /// JSON file of exchange rates used to add prices in a reference currency
+        #[clap(long, requires = "all_countries")]
+        rates: Option<PathBuf>,
+        /// Time to wait between countries in milliseconds
+        #[clap(long, default_value = "500")]
+        delay: u64,
     },
     /// Perform a search for a given query string
     Search {
@@ -315,10 +473,27 @@ enum ApiCommand {
         #[clap(long, default_value = "500")]
         delay: u64,
     },
+    /// List the apps in a category by code (for example `GAME_PUZZLE`)
+    Category {
+        #[clap(long)]
+        code: String,
+        /// Ranked collection (topselling_free, topselling_paid, or topgrossing)
+        #[clap(long)]
+        collection: Option<Collection>,
+        /// Download full app information for results
+        #[clap(long)]
+        full: bool,
+        /// Time to wait between app detail requests in milliseconds
+        #[clap(long, default_value = "500")]
+        delay: u64,
+    },
     /// Request a list of apps for a developer by ID (may be an integer or a string)
     Developer {
         #[clap(long)]
         id: DeveloperId,
+        /// Download full app information for each app
+        #[clap(long)]
+        hydrate: bool,
     },
     /// Look up reviews for an app by ID
     Reviews {
@@ -336,10 +511,40 @@ enum ApiCommand {
 enum StoreCommand {
     Apps,
     Search,
+    /// Print archived autocomplete suggestions
+    Suggestions,
+    /// Detect edits to reviews (by review ID) across archived review pages
+    ReviewHistory,
+    /// Maintain and query a local SQLite index of the store
+    Index {
+        #[clap(subcommand)]
+        command: IndexCommand,
+    },
+    /// Export apps, developers, reviews, and search results as Parquet files
+    Export {
+        #[clap(long, default_value = "data/export/google/")]
+        output: PathBuf,
+    },
     #[cfg(not(feature = "strict"))]
     FixStrict,
 }
 
+#[derive(Debug, Parser)]
+enum IndexCommand {
+    /// Add any archive files that have not already been indexed
+    Build {
+        #[clap(long, default_value = "data/google.db")]
+        index: PathBuf,
+    },
+    /// Run a SQL query against the index and print the results
+    Query {
+        #[clap(long, default_value = "data/google.db")]
+        index: PathBuf,
+        #[clap(long)]
+        sql: String,
+    },
+}
+
 #[derive(Clone, Copy, Debug, clap::ValueEnum, serde::Deserialize)]
 #[serde(rename_all = "lowercase")]
 enum PriceFilter {
@@ -355,3 +560,175 @@ impl From<PriceFilter> for app_store_access_google::request::params::search::Pri
         }
     }
 }
+
+#[derive(serde::Serialize)]
+struct AppRecord<'a> {
+    app_id: &'a str,
+    developer_id: String,
+    title: &'a str,
+    developer_name: &'a str,
+}
+
+#[derive(serde::Serialize)]
+struct StoreAppRecord<'a> {
+    app_id: &'a str,
+    developer_id: String,
+    contains_ads: bool,
+    in_app_purchases: bool,
+    in_app_purchase_range: Option<&'a str>,
+    pre_registration: bool,
+    early_access: bool,
+}
+
+impl<'a> StoreAppRecord<'a> {
+    fn new(app: &'a app_store_access_google::model::app::App) -> Self {
+        Self {
+            app_id: &app.app_id,
+            developer_id: app.developer.id.to_string(),
+            contains_ads: app.monetization.contains_ads,
+            in_app_purchases: app.monetization.in_app_purchases(),
+            in_app_purchase_range: app.monetization.in_app_purchase_range.as_deref(),
+            pre_registration: app.monetization.pre_registration,
+            early_access: app.monetization.early_access,
+        }
+    }
+}
+
+/// The availability of an app in a single country, with its localized title, price, and rating.
+#[derive(serde::Serialize)]
+struct AvailabilityRecord<'a> {
+    app_id: &'a str,
+    #[serde(with = "app_store_access::country::country_code")]
+    country: Country,
+    available: bool,
+    title: Option<&'a str>,
+    price: Option<f64>,
+    currency: Option<&'a str>,
+    reference_price: Option<app_store_access::money::Amount>,
+    reference_currency: Option<app_store_access::money::CurrencyCode>,
+    rating: Option<f64>,
+    rating_count: Option<u64>,
+}
+
+impl<'a> AvailabilityRecord<'a> {
+    fn new(
+        app_id: &'a str,
+        country: Country,
+        app: Option<&'a app_store_access_google::model::app::App>,
+        rates: Option<&app_store_access::money::Rates>,
+    ) -> Self {
+        let price = app.and_then(|app| app.price.as_ref());
+        let money = price.and_then(app_store_access_google::model::Price::money);
+        let reference = money
+            .zip(rates)
+            .and_then(|(money, rates)| money.convert(rates));
+        let rating = app.and_then(|app| app.rating);
+
+        Self {
+            app_id,
+            country,
+            available: app.is_some(),
+            title: app.map(|app| app.title.as_str()),
+            price: money.map(|money| money.amount.to_f64()),
+            currency: price.map(|price| price.currency.as_str()),
+            reference_price: reference.map(|money| money.amount),
+            reference_currency: reference.map(|money| money.currency),
+            rating: rating.map(|rating| rating.score),
+            rating_count: rating.map(|rating| rating.count),
+        }
+    }
+}
+
+#[derive(serde::Serialize)]
+struct SearchResultRecord<'a> {
+    app_id: &'a str,
+    developer_id: Option<String>,
+    title: &'a str,
+    developer_name: &'a str,
+    free: bool,
+    price: Option<app_store_access::money::Amount>,
+    original_price: Option<app_store_access::money::Amount>,
+    currency: Option<&'a str>,
+    price_formatted: Option<&'a str>,
+}
+
+impl<'a> SearchResultRecord<'a> {
+    fn new(app: &'a app_store_access_google::model::search::App) -> Self {
+        let current = app.price.as_ref().and_then(|price| price.current());
+        let original = app.price.as_ref().and_then(|price| price.original());
+
+        Self {
+            app_id: &app.id,
+            developer_id: app
+                .developer_id()
+                .map(|developer_id| developer_id.to_string()),
+            title: &app.title,
+            developer_name: &app.developer.name,
+            free: app.is_free(),
+            price: current
+                .and_then(|price| price.money())
+                .map(|money| money.amount),
+            original_price: original
+                .and_then(|price| price.money())
+                .map(|money| money.amount),
+            currency: current.map(|price| price.currency.as_str()),
+            price_formatted: current.and_then(|price| price.display.as_deref()),
+        }
+    }
+}
+
+#[derive(serde::Serialize)]
+struct DeveloperAppRecord<'a> {
+    app_id: &'a str,
+    developer_id: String,
+    developer_name: &'a str,
+    developer_website: Option<&'a str>,
+    featured: bool,
+    title: Option<&'a str>,
+}
+
+#[derive(serde::Serialize)]
+struct ReviewRecord<'a> {
+    id: uuid::Uuid,
+    // Google user IDs are too large for some JSON consumers.
+    user_id: Option<String>,
+    user_name: &'a str,
+}
+
+#[derive(serde::Serialize)]
+struct ReviewEditRecord<'a> {
+    review_id: uuid::Uuid,
+    app_id: &'a str,
+    before_timestamp: chrono::DateTime<chrono::Utc>,
+    after_timestamp: chrono::DateTime<chrono::Utc>,
+    before_score: u8,
+    after_score: u8,
+    text_changed: bool,
+}
+
+#[derive(serde::Serialize)]
+struct SuggestionRecord<'a> {
+    query: &'a str,
+    #[serde(with = "app_store_access::language::language_code")]
+    lang: Language,
+    #[serde(with = "app_store_access::country::country_code")]
+    country: Country,
+    suggestion: String,
+}
+
+#[derive(serde::Serialize)]
+struct StoreSearchRecord<'a> {
+    query: Option<&'a str>,
+    price: Option<String>,
+    app_id: &'a str,
+    developer_id: &'a str,
+    request_token: Option<&'a str>,
+    token: Option<&'a str>,
+}
+
+#[cfg(not(feature = "strict"))]
+#[derive(serde::Serialize)]
+struct FixRecord<'a> {
+    kind: &'static str,
+    value: &'a str,
+}
@@ -1,4 +1,6 @@
-use crate::request::params::{developer::DeveloperId, review::SortOrder, search::PriceFilter};
+use crate::request::params::{
+    category::Collection, developer::DeveloperId, review::SortOrder, search::PriceFilter,
+};
 use app_store_access::{country::Country, language::Language};
 use reqwest::StatusCode;
 use scraper_trail::{client::text_send, request::params::Params};
@@ -93,7 +95,8 @@ impl Client {
         language: Language,
         country: Country,
         number: usize,
-    ) -> Result<Option<Vec<crate::model::app::AppMetadata>>, Error> {
+        hydrate: bool,
+    ) -> Result<Option<crate::model::developer::Portfolio>, Error> {
         use crate::model::developer::pagination::{
             InitialNameIdResponse, InitialNumericIdResponse, PaginatedNameIdResponse,
             PaginatedNumericIdResponse,
@@ -115,6 +118,9 @@ impl Client {
                     new_exchange.save_file(output)?;
                 }
 
+                let profile =
+                    crate::model::developer::Profile::from_page(developer.clone(), &json_3)?;
+
                 let page: crate::model::developer::PageResponse = if developer.is_numeric() {
                     serde_json::from_value::<InitialNumericIdResponse>(json_3)?.into()
                 } else {
@@ -154,7 +160,26 @@ impl Client {
                     token = page.token;
                 }
 
-                Ok(Some(apps))
+                let details = if hydrate {
+                    let mut details = Vec::with_capacity(apps.len());
+
+                    for app in &apps {
+                        match self.app(&app.app_id, language, country).await? {
+                            Some(app) => details.push(app),
+                            None => log::warn!("Developer app not found: {}", app.app_id),
+                        }
+                    }
+
+                    Some(details)
+                } else {
+                    None
+                };
+
+                Ok(Some(crate::model::developer::Portfolio {
+                    profile,
+                    apps,
+                    details,
+                }))
             }
             Err(scraper_trail::client::Error::UnexpectedStatus {
                 status_code: StatusCode::NOT_FOUND,
@@ -189,18 +214,85 @@ impl Client {
 
         let search_result: crate::model::search::SearchResult = serde_json::from_value(json)?;
 
-        let mut apps = vec![];
-        let mut token = search_result.token().map(str::to_string);
+        let token = search_result.token().map(str::to_string);
+        let apps = search_result
+            .inner
+            .map(|inner| inner.apps)
+            .unwrap_or_default();
+
+        self.search_pagination(apps, token, None, language, country, number)
+            .await
+    }
+
+    /// List the apps in a category (or one of its ranked collections).
+    pub async fn category(
+        &self,
+        code: &str,
+        collection: Option<Collection>,
+        language: Language,
+        country: Country,
+        number: usize,
+    ) -> Result<Vec<crate::model::search::App>, Error> {
+        let request = crate::request::Request::category(code, collection, language, country);
+        let exchange = text_send(&self.underlying, request.build_request(None)).await?;
+
+        // Same scoping trick as in `developer()` to keep the future `Send`.
+        let json = {
+            let html = scraper::Html::parse_document(&exchange.response.data);
+            crate::parse::parse_ds_value::<Value>(&html, 3)?
+        };
+
+        let new_exchange = exchange.map(|_| serde_json::json!(vec![json.clone()]));
 
-        if let Some(search_results) = search_result.inner {
-            apps.extend(search_results.apps);
+        if let Some(output) = &self.output {
+            new_exchange.save_file(output)?;
         }
 
+        let category_result: crate::model::category::CategoryResult = serde_json::from_value(json)?;
+
+        let token = category_result.token().map(str::to_string);
+        let apps = category_result
+            .inner
+            .map(|inner| inner.apps)
+            .unwrap_or_default();
+
+        self.search_pagination(
+            apps,
+            token,
+            Some((code, collection)),
+            language,
+            country,
+            number,
+        )
+        .await
+    }
+
+    /// Follow pagination tokens for search results and category listings.
+    ///
+    /// For category listings, the category code and collection are recorded with each request.
+    async fn search_pagination(
+        &self,
+        mut apps: Vec<crate::model::search::App>,
+        mut token: Option<String>,
+        category: Option<(&str, Option<Collection>)>,
+        language: Language,
+        country: Country,
+        number: usize,
+    ) -> Result<Vec<crate::model::search::App>, Error> {
         while let Some(ref token_value) = token {
             log::info!("Making search pagination request via token: {token_value}");
 
-            let request =
-                crate::request::Request::pagination(number, token_value, language, country);
+            let request = match category {
+                Some((code, collection)) => crate::request::Request::category_pagination(
+                    code,
+                    collection,
+                    number,
+                    token_value,
+                    language,
+                    country,
+                ),
+                None => crate::request::Request::pagination(number, token_value, language, country),
+            };
 
             let exchange =
                 scraper_trail::client::text_send(&self.underlying, request.build_request(None))
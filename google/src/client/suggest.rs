@@ -1,12 +1,14 @@
+use scraper_trail::{client::text_send, request::params::Params};
 use serde_json::Value;
 
-const BASE_URL: &str = "https://play.google.com";
-const URL: &str = "/_/PlayStoreUi/data/batchexecute?rpcids=IJ4APc&f.sid=-697906427155521722&bl=boq_playuiserver_20190903.08_p0&authuser&soc-app=121&soc-platform=1&soc-device=1&_reqid=1065213";
-
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
+    #[error("I/O error")]
+    Io(#[from] std::io::Error),
     #[error("HTTP client error")]
     Reqwest(#[from] reqwest::Error),
+    #[error("Scraper client error")]
+    ScraperClient(#[from] scraper_trail::client::Error),
     #[error("JSON error")]
     Json(#[from] serde_json::Error),
     #[error("Too short")]
@@ -26,29 +28,18 @@ impl app_store_access::client::SuggestionClient for super::Client {
         country: app_store_access::country::Country,
         lang: app_store_access::language::Language,
     ) -> impl std::future::Future<Output = Result<Vec<String>, Self::Error>> + Send {
-        let url = format!("{BASE_URL}{URL}&hl={lang}&gl={country}");
+        let request = crate::request::Request::suggest(query, lang, country);
 
-        // Build the body safely: construct the nested JSON programmatically so
-        // that `query` is properly JSON-escaped, then URL-encode the whole value.
         async move {
-            let inner_json = serde_json::json!([[null, [query], [10], [2], 4]]).to_string();
-            let outer_json = serde_json::json!([[["IJ4APc", inner_json]]]).to_string();
-            let body = format!("f.req={}", urlencoding::encode(&outer_json));
-
-            let response = self
-                .underlying
-                .post(url)
-                .header(
-                    reqwest::header::CONTENT_TYPE,
-                    "application/x-www-form-urlencoded;charset=UTF-8",
-                )
-                .body(body)
-                .send()
-                .await?;
+            let exchange = text_send(&self.underlying, request.build_request(None)).await?;
+            let suggestions = exchange.response.data.parse::<SuggestionList>()?.0;
+            let new_exchange = exchange.map(|_| serde_json::json!(suggestions));
 
-            let body = response.text().await?;
+            if let Some(output) = &self.output {
+                new_exchange.save_file(output)?;
+            }
 
-            Ok(body.parse::<SuggestionList>()?.0)
+            Ok(suggestions)
         }
     }
 }
@@ -0,0 +1,368 @@
+use crate::{
+    archive::Data,
+    model::{app::App, review::Review, search},
+    request::RequestData,
+};
+use app_store_access::export::{
+    Record, Snapshot, Stats, Writer, f64_column, f64_field, snapshot_columns, snapshot_schema,
+    string_column, string_field, timestamp_column, timestamp_field, u64_column, u64_field,
+};
+use arrow::array::ArrayRef;
+use arrow::datatypes::SchemaRef;
+use chrono::{DateTime, Utc};
+use std::path::Path;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Export error")]
+    Export(#[from] app_store_access::export::Error),
+    #[error("I/O error")]
+    Io(#[from] std::io::Error),
+}
+
+/// Export all archived apps, developers, reviews, and search results as Parquet files in the
+/// given output directory.
+///
+/// As when building an index, archive files that cannot be read or parsed are logged and skipped.
+pub fn export<P: AsRef<Path>, Q: AsRef<Path>>(
+    archive: P,
+    output: Q,
+    most_recent_first: bool,
+) -> Result<Stats, Error> {
+    let output = output.as_ref();
+    std::fs::create_dir_all(output)?;
+
+    let mut apps = Writer::<AppRecord>::create(output.join("apps.parquet"))?;
+    let mut developers = Writer::<DeveloperRecord>::create(output.join("developers.parquet"))?;
+    let mut reviews = Writer::<ReviewRecord>::create(output.join("reviews.parquet"))?;
+    let mut search_results =
+        Writer::<SearchResultRecord>::create(output.join("search_results.parquet"))?;
+
+    let store = scraper_trail::archive::store::Store::new(archive.as_ref());
+    let mut failed = 0;
+
+    for (path, entry) in store.entries::<Data>(most_recent_first)? {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(error) => {
+                log::warn!("Skipping archive file {}: {error:?}", path.display());
+                failed += 1;
+                continue;
+            }
+        };
+        let request = &entry.request_params;
+
+        let snapshot = Snapshot {
+            timestamp: entry.exchange.request.timestamp,
+            country: Some(request.country),
+            language: Some(request.language),
+        };
+
+        match entry.exchange.response.data {
+            Data::Detail(app) => {
+                apps.write(AppRecord::new(snapshot, &app))?;
+                developers.write(DeveloperRecord {
+                    snapshot,
+                    developer_id: app.developer.id.to_string(),
+                    app_id: app.app_id,
+                })?;
+            }
+            Data::DeveloperInitial(page) | Data::DeveloperPagination(_, page) => {
+                for app in page.apps {
+                    developers.write(DeveloperRecord {
+                        snapshot,
+                        developer_id: app.developer_id.to_string(),
+                        app_id: app.app_id,
+                    })?;
+                }
+            }
+            Data::Search(page) => {
+                let query = match &request.data {
+                    RequestData::Search { query, .. } => Some(query.to_string()),
+                    _ => None,
+                };
+
+                for (position, app) in page.apps.unwrap_or_default().into_iter().enumerate() {
+                    search_results.write(SearchResultRecord::new(
+                        snapshot,
+                        query.clone(),
+                        position,
+                        app,
+                    ))?;
+                }
+            }
+            Data::Reviews(page) => {
+                if let RequestData::Reviews { app_id, .. } = &request.data {
+                    for review in page.reviews {
+                        reviews.write(ReviewRecord::new(snapshot, app_id.to_string(), review))?;
+                    }
+                }
+            }
+            // Category listings only contain summary information, which the export does not use.
+            Data::Category(_) | Data::Suggestions(_) => {}
+        }
+    }
+
+    Ok(Stats {
+        apps: apps.finish()?,
+        developers: developers.finish()?,
+        reviews: reviews.finish()?,
+        search_results: search_results.finish()?,
+        failed,
+    })
+}
+
+/// An app from a details page.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AppRecord {
+    pub snapshot: Snapshot,
+    pub app_id: String,
+    pub title: String,
+    pub summary: String,
+    pub developer_id: String,
+    pub developer_name: String,
+    pub developer_email: String,
+    pub developer_website: Option<String>,
+    pub genre_id: String,
+    pub genre_name: String,
+    pub content_rating: String,
+    pub released: Option<DateTime<Utc>>,
+    pub price: Option<f64>,
+    pub currency: Option<String>,
+    pub icon: String,
+}
+
+impl AppRecord {
+    fn new(snapshot: Snapshot, app: &App) -> Self {
+        Self {
+            snapshot,
+            app_id: app.app_id.clone(),
+            title: app.title.clone(),
+            summary: app.summary.clone(),
+            developer_id: app.developer.id.to_string(),
+            developer_name: app.developer.name.clone(),
+            developer_email: app.developer.email.clone(),
+            developer_website: app.developer.website.clone(),
+            genre_id: app.genre.id.clone(),
+            genre_name: app.genre.name.clone(),
+            content_rating: app.content_rating.name.clone(),
+            released: app.released,
+            price: app
+                .price
+                .as_ref()
+                .and_then(crate::model::Price::money)
+                .map(|money| money.amount.to_f64()),
+            currency: app.price.as_ref().map(|price| price.currency.clone()),
+            icon: app.images.icon.clone(),
+        }
+    }
+}
+
+impl Record for AppRecord {
+    fn schema() -> SchemaRef {
+        snapshot_schema([
+            string_field("app_id", false),
+            string_field("title", false),
+            string_field("summary", false),
+            string_field("developer_id", false),
+            string_field("developer_name", false),
+            string_field("developer_email", false),
+            string_field("developer_website", true),
+            string_field("genre_id", false),
+            string_field("genre_name", false),
+            string_field("content_rating", false),
+            timestamp_field("released", true),
+            f64_field("price", true),
+            string_field("currency", true),
+            string_field("icon", false),
+        ])
+    }
+
+    fn columns(records: &[Self]) -> Vec<ArrayRef> {
+        snapshot_columns(
+            records,
+            |record| &record.snapshot,
+            [
+                string_column(records, |record| Some(record.app_id.as_str())),
+                string_column(records, |record| Some(record.title.as_str())),
+                string_column(records, |record| Some(record.summary.as_str())),
+                string_column(records, |record| Some(record.developer_id.as_str())),
+                string_column(records, |record| Some(record.developer_name.as_str())),
+                string_column(records, |record| Some(record.developer_email.as_str())),
+                string_column(records, |record| record.developer_website.as_deref()),
+                string_column(records, |record| Some(record.genre_id.as_str())),
+                string_column(records, |record| Some(record.genre_name.as_str())),
+                string_column(records, |record| Some(record.content_rating.as_str())),
+                timestamp_column(records, |record| record.released),
+                f64_column(records, |record| record.price),
+                string_column(records, |record| record.currency.as_deref()),
+                string_column(records, |record| Some(record.icon.as_str())),
+            ],
+        )
+    }
+}
+
+/// An association between a developer and one of their apps.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DeveloperRecord {
+    pub snapshot: Snapshot,
+    pub developer_id: String,
+    pub app_id: String,
+}
+
+impl Record for DeveloperRecord {
+    fn schema() -> SchemaRef {
+        snapshot_schema([
+            string_field("developer_id", false),
+            string_field("app_id", false),
+        ])
+    }
+
+    fn columns(records: &[Self]) -> Vec<ArrayRef> {
+        snapshot_columns(
+            records,
+            |record| &record.snapshot,
+            [
+                string_column(records, |record| Some(record.developer_id.as_str())),
+                string_column(records, |record| Some(record.app_id.as_str())),
+            ],
+        )
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReviewRecord {
+    pub snapshot: Snapshot,
+    pub app_id: String,
+    pub review_id: String,
+    pub user_id: Option<String>,
+    pub user_name: String,
+    pub score: u8,
+    pub timestamp: DateTime<Utc>,
+    pub text: Option<String>,
+    pub thumbs_up: Option<usize>,
+    pub version: Option<String>,
+    pub reply_text: Option<String>,
+    pub reply_timestamp: Option<DateTime<Utc>>,
+}
+
+impl ReviewRecord {
+    fn new(snapshot: Snapshot, app_id: String, review: Review) -> Self {
+        let (reply_text, reply_timestamp) = review
+            .reply
+            .map(|reply| (reply.text, reply.timestamp))
+            .unzip();
+
+        Self {
+            snapshot,
+            app_id,
+            review_id: review.id.to_string(),
+            // Google user IDs do not fit in any Parquet integer type.
+            user_id: review.user.id.map(|id| id.to_string()),
+            user_name: review.user.display_name,
+            score: review.score,
+            timestamp: review.timestamp,
+            text: review.text,
+            thumbs_up: review.thumbs_up,
+            version: review.version,
+            reply_text,
+            reply_timestamp,
+        }
+    }
+}
+
+impl Record for ReviewRecord {
+    fn schema() -> SchemaRef {
+        snapshot_schema([
+            string_field("app_id", false),
+            string_field("review_id", false),
+            string_field("user_id", true),
+            string_field("user_name", false),
+            u64_field("score", false),
+            timestamp_field("timestamp", false),
+            string_field("text", true),
+            u64_field("thumbs_up", true),
+            string_field("version", true),
+            string_field("reply_text", true),
+            timestamp_field("reply_timestamp", true),
+        ])
+    }
+
+    fn columns(records: &[Self]) -> Vec<ArrayRef> {
+        snapshot_columns(
+            records,
+            |record| &record.snapshot,
+            [
+                string_column(records, |record| Some(record.app_id.as_str())),
+                string_column(records, |record| Some(record.review_id.as_str())),
+                string_column(records, |record| record.user_id.as_deref()),
+                string_column(records, |record| Some(record.user_name.as_str())),
+                u64_column(records, |record| Some(u64::from(record.score))),
+                timestamp_column(records, |record| Some(record.timestamp)),
+                string_column(records, |record| record.text.as_deref()),
+                u64_column(records, |record| record.thumbs_up.map(|value| value as u64)),
+                string_column(records, |record| record.version.as_deref()),
+                string_column(records, |record| record.reply_text.as_deref()),
+                timestamp_column(records, |record| record.reply_timestamp),
+            ],
+        )
+    }
+}
+
+/// A single result on a search page, with its zero-indexed position on that page.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SearchResultRecord {
+    pub snapshot: Snapshot,
+    pub query: Option<String>,
+    pub position: usize,
+    pub app_id: String,
+    pub title: String,
+    pub developer_id: Option<String>,
+    pub developer_name: String,
+    pub score: Option<f32>,
+}
+
+impl SearchResultRecord {
+    fn new(snapshot: Snapshot, query: Option<String>, position: usize, app: search::App) -> Self {
+        Self {
+            snapshot,
+            query,
+            position,
+            developer_id: app.developer_id().map(|id| id.to_string()),
+            app_id: app.id,
+            title: app.title,
+            developer_name: app.developer.name,
+            score: app.score.map(|score| score.value),
+        }
+    }
+}
+
+impl Record for SearchResultRecord {
+    fn schema() -> SchemaRef {
+        snapshot_schema([
+            string_field("query", true),
+            u64_field("position", false),
+            string_field("app_id", false),
+            string_field("title", false),
+            string_field("developer_id", true),
+            string_field("developer_name", false),
+            f64_field("score", true),
+        ])
+    }
+
+    fn columns(records: &[Self]) -> Vec<ArrayRef> {
+        snapshot_columns(
+            records,
+            |record| &record.snapshot,
+            [
+                string_column(records, |record| record.query.as_deref()),
+                u64_column(records, |record| Some(record.position as u64)),
+                string_column(records, |record| Some(record.app_id.as_str())),
+                string_column(records, |record| Some(record.title.as_str())),
+                string_column(records, |record| record.developer_id.as_deref()),
+                string_column(records, |record| Some(record.developer_name.as_str())),
+                f64_column(records, |record| record.score.map(f64::from)),
+            ],
+        )
+    }
+}
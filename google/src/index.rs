@@ -0,0 +1,157 @@
+use crate::{archive::Data, request::RequestData};
+use app_store_access::index::{
+    App, Developer, Index, Review, SearchResult, SnapshotInfo, Stats, Store,
+};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Index error")]
+    Index(#[from] app_store_access::index::Error),
+    #[error("I/O error")]
+    Io(#[from] std::io::Error),
+    #[error("JSON file error")]
+    JsonFile(PathBuf, serde_json::Error),
+}
+
+/// Ingest all archive files that are not already present in the index.
+///
+/// Files that cannot be read or parsed are logged and skipped, and will be retried on the next run.
+pub fn build<P: AsRef<Path>>(archive: P, index: &mut Index) -> Result<Stats, Error> {
+    let archive = archive.as_ref();
+    let mut stats = Stats::default();
+
+    for path in app_store_access::index::archive_paths(archive)? {
+        if index.is_ingested(Store::Google, &path)? {
+            stats.skipped += 1;
+        } else {
+            match ingest_file(archive, &path, index) {
+                Ok(()) => {
+                    stats.ingested += 1;
+                }
+                Err(Error::Index(error)) => return Err(Error::Index(error)),
+                Err(error) => {
+                    log::warn!("Skipping archive file {}: {error:?}", path.display());
+                    stats.failed += 1;
+                }
+            }
+        }
+    }
+
+    Ok(stats)
+}
+
+fn ingest_file(archive: &Path, path: &Path, index: &mut Index) -> Result<(), Error> {
+    let full_path = archive.join(path);
+    let contents = std::fs::read_to_string(&full_path)?;
+    let entry = serde_json::from_str::<scraper_trail::archive::entry::Entry<Data>>(&contents)
+        .map_err(|error| Error::JsonFile(full_path.clone(), error))?;
+
+    let request = &entry.request_params;
+    let data = &entry.exchange.response.data;
+
+    let info = SnapshotInfo {
+        kind: kind(data),
+        timestamp: entry.exchange.request.timestamp,
+        country: Some(request.country),
+        language: Some(request.language),
+    };
+
+    index.ingest(Store::Google, path, &info, |snapshot| {
+        match data {
+            Data::Detail(app) => {
+                let developer_id = app.developer.id.to_string();
+
+                snapshot.add_app(&App {
+                    app_id: &app.app_id,
+                    bundle_id: None,
+                    developer_id: Some(&developer_id),
+                    title: &app.title,
+                    developer_name: Some(&app.developer.name),
+                })?;
+
+                snapshot.add_developer(&Developer {
+                    developer_id: &developer_id,
+                    name: Some(&app.developer.name),
+                    app_id: Some(&app.app_id),
+                })?;
+            }
+            Data::DeveloperInitial(page) | Data::DeveloperPagination(_, page) => {
+                for app in &page.apps {
+                    snapshot.add_developer(&Developer {
+                        developer_id: &app.developer_id.to_string(),
+                        name: None,
+                        app_id: Some(&app.app_id),
+                    })?;
+                }
+            }
+            Data::Search(page) => {
+                let query = match &request.data {
+                    RequestData::Search { query, .. } => Some(query.as_ref()),
+                    _ => None,
+                };
+
+                for (position, app) in page.apps.iter().flatten().enumerate() {
+                    let developer_id = app.developer_id().map(|id| id.to_string());
+
+                    snapshot.add_app(&App {
+                        app_id: &app.id,
+                        bundle_id: None,
+                        developer_id: developer_id.as_deref(),
+                        title: &app.title,
+                        developer_name: Some(&app.developer.name),
+                    })?;
+
+                    snapshot.add_search_result(&SearchResult {
+                        query,
+                        position,
+                        app_id: &app.id,
+                    })?;
+                }
+            }
+            Data::Category(page) => {
+                for app in page.apps.iter().flatten() {
+                    let developer_id = app.developer_id().map(|id| id.to_string());
+
+                    snapshot.add_app(&App {
+                        app_id: &app.id,
+                        bundle_id: None,
+                        developer_id: developer_id.as_deref(),
+                        title: &app.title,
+                        developer_name: Some(&app.developer.name),
+                    })?;
+                }
+            }
+            Data::Reviews(page) => {
+                if let RequestData::Reviews { app_id, .. } = &request.data {
+                    for review in &page.reviews {
+                        snapshot.add_review(&Review {
+                            app_id,
+                            review_id: &review.id.to_string(),
+                            user_name: &review.user.display_name,
+                            score: review.score,
+                            timestamp: Some(review.timestamp),
+                            text: review.text.as_deref(),
+                        })?;
+                    }
+                }
+            }
+            Data::Suggestions(_) => {}
+        }
+
+        Ok(())
+    })?;
+
+    Ok(())
+}
+
+const fn kind(data: &Data) -> &'static str {
+    match data {
+        Data::Detail(_) => "detail",
+        Data::DeveloperInitial(_) | Data::DeveloperPagination(_, _) => "developer",
+        Data::Search(_) => "search",
+        Data::Category(_) => "category",
+        Data::Reviews(_) => "reviews",
+        Data::Suggestions(_) => "suggestions",
+    }
+}
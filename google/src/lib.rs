@@ -3,6 +3,10 @@
 #![forbid(unsafe_code)]
 pub mod archive;
 pub mod client;
+pub mod export;
+pub mod index;
+pub mod media;
 pub mod model;
 pub mod parse;
+pub mod rank;
 pub mod request;
@@ -0,0 +1,103 @@
+//! Media assets listed on archived Google app detail pages.
+use crate::{archive::Data, model::app::App};
+use app_store_access::{
+    country::Country,
+    index::Store,
+    language::Language,
+    media::{Asset, Snapshot},
+    model::ImageType,
+};
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("I/O error")]
+    Io(#[from] std::io::Error),
+    #[error("Archive error")]
+    Store(PathBuf, scraper_trail::archive::store::Error),
+}
+
+/// Request an image at most `width` pixels wide.
+///
+/// Image URLs accept a size suffix; URLs that already include one are left unchanged.
+#[must_use]
+pub fn url_for_width(url: &str, width: usize) -> String {
+    if url.contains('=') {
+        url.to_string()
+    } else {
+        format!("{url}=w{width}")
+    }
+}
+
+/// The icon, header image, and screenshots for an app (Google does not list preview videos).
+#[must_use]
+pub fn assets(app: &App, width: usize) -> Vec<Asset> {
+    let mut positions = HashMap::<ImageType, usize>::new();
+
+    app.images
+        .urls()
+        .filter(|(url, _)| !url.is_empty())
+        .map(|(url, image_type)| {
+            let position = positions.entry(image_type).or_default();
+            let asset = Asset {
+                image_type,
+                device: None,
+                position: *position,
+                url: url_for_width(url, width),
+            };
+
+            *position += 1;
+
+            asset
+        })
+        .collect()
+}
+
+/// Build a snapshot from an app listing.
+#[must_use]
+pub fn snapshot(
+    timestamp: DateTime<Utc>,
+    country: Country,
+    language: Language,
+    app: &App,
+    width: usize,
+) -> Snapshot {
+    Snapshot {
+        store: Store::Google,
+        app_id: app.app_id.clone(),
+        timestamp,
+        country,
+        language: Some(language),
+        assets: assets(app, width),
+    }
+}
+
+/// Build snapshots from all archived detail pages (optionally only for the given apps).
+pub fn snapshots<P: AsRef<Path>>(
+    archive: P,
+    app_ids: &[String],
+    width: usize,
+) -> Result<Vec<Snapshot>, Error> {
+    let store = scraper_trail::archive::store::Store::new(archive.as_ref());
+    let mut snapshots = vec![];
+
+    for (path, entry) in store.entries::<Data>(false)? {
+        let entry = entry.map_err(|error| Error::Store(path, error))?;
+
+        if let Data::Detail(app) = &entry.exchange.response.data
+            && (app_ids.is_empty() || app_ids.contains(&app.app_id))
+        {
+            snapshots.push(snapshot(
+                entry.exchange.request.timestamp,
+                entry.request_params.country,
+                entry.request_params.language,
+                app,
+                width,
+            ));
+        }
+    }
+
+    Ok(snapshots)
+}
@@ -2,7 +2,7 @@ use crate::request::params::developer::DeveloperId;
 use app_store_access::model::ImageType;
 use chrono::{DateTime, Utc};
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
 pub struct AppMetadata {
     pub app_id: String,
     pub developer_id: DeveloperId,
@@ -18,7 +18,7 @@ impl AppMetadata {
     }
 }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, serde::Serialize)]
 pub struct App {
     pub app_id: String,
     pub developer: super::developer::Developer,
@@ -30,6 +30,9 @@ pub struct App {
     pub genre: Genre,
     pub content_rating: super::ContentRating,
     pub images: Images,
+    pub monetization: Monetization,
+    /// Missing for apps that have not been rated yet.
+    pub rating: Option<Rating>,
 }
 
 impl App {
@@ -50,6 +53,7 @@ impl<'de> serde::de::Deserialize<'de> for App {
             )
         })?;
         let price = internal.price();
+        let monetization = internal.monetization();
 
         Ok(Self {
             app_id: internal.id,
@@ -85,11 +89,41 @@ impl<'de> serde::de::Deserialize<'de> for App {
                 header: internal.header_image,
                 screenshots: internal.screenshots,
             },
+            monetization,
+            rating: internal.rating_section.map(|rating| Rating {
+                score: rating.score,
+                count: rating.count,
+            }),
         })
     }
 }
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+/// Average star rating and the number of ratings it is based on.
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize)]
+pub struct Rating {
+    pub score: f64,
+    pub count: u64,
+}
+
+/// Advertising, in-app purchase, and release status flags.
+#[derive(Clone, Debug, Default, Eq, PartialEq, serde::Serialize)]
+pub struct Monetization {
+    pub contains_ads: bool,
+    /// The localized price range label (for example "$0.99 - $49.99 per item"), which is only
+    /// present for apps that offer in-app purchases.
+    pub in_app_purchase_range: Option<String>,
+    pub pre_registration: bool,
+    pub early_access: bool,
+}
+
+impl Monetization {
+    #[must_use]
+    pub const fn in_app_purchases(&self) -> bool {
+        self.in_app_purchase_range.is_some()
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
 pub struct Images {
     pub icon: String,
     pub header: String,
@@ -108,7 +142,7 @@ impl Images {
     }
 }
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
 pub struct Genre {
     pub id: String,
     pub name: String,
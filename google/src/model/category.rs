@@ -0,0 +1,26 @@
+//! Category listings use the same app entries and pagination tokens as search results.
+use super::search::{Page, SearchResultInner};
+
+#[derive(Clone, Debug, PartialEq, serde_query::Deserialize, serde::Serialize)]
+pub struct CategoryResult {
+    #[query(".data.[0].[1]")]
+    pub inner: Option<SearchResultInner>,
+}
+
+impl CategoryResult {
+    #[must_use]
+    pub fn token(&self) -> Option<&str> {
+        self.inner.as_ref().and_then(SearchResultInner::token)
+    }
+}
+
+impl From<CategoryResult> for Page {
+    fn from(value: CategoryResult) -> Self {
+        let token = value.token().map(std::string::ToString::to_string);
+
+        Self {
+            apps: value.inner.map(|inner| inner.apps),
+            token,
+        }
+    }
+}
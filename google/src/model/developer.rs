@@ -1,12 +1,12 @@
 use crate::{model::app::AppMetadata, request::params::developer::DeveloperId};
 
-#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, serde::Serialize)]
 pub struct Page {
     pub apps: Vec<AppMetadata>,
     pub token: Option<String>,
 }
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
 pub struct Developer {
     pub id: DeveloperId,
     pub name: String,
@@ -18,22 +18,83 @@ pub struct Developer {
     pub legal_address: Option<String>,
 }
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+/// The header of a developer page.
+///
+/// Only pages for numeric developer IDs have a header, so for name IDs every field except the name
+/// (which is the ID itself) is empty.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+pub struct Profile {
+    pub id: DeveloperId,
+    pub name: String,
+    pub description: Option<String>,
+    pub header_image: Option<String>,
+    pub icon: Option<String>,
+    pub website: Option<String>,
+    pub featured_app_id: Option<String>,
+}
+
+impl Profile {
+    /// Read the profile from the `ds:3` value of a developer page.
+    ///
+    /// Fails if a page for a numeric developer ID does not have the expected header layout.
+    pub fn from_page(
+        id: DeveloperId,
+        value: &serde_json::Value,
+    ) -> Result<Self, serde_json::Error> {
+        match &id {
+            DeveloperId::Numeric(_) => {
+                let page = <internal::ProfilePage as serde::Deserialize>::deserialize(value)?;
+
+                Ok(Self {
+                    name: page.header.name,
+                    description: page.header.description,
+                    header_image: page.header.header_image.map(|image| image.url),
+                    icon: page.header.icon.map(|image| image.url),
+                    website: page.header.website.map(|link| link.url),
+                    featured_app_id: page.featured_app.map(|app| app.app_id),
+                    id,
+                })
+            }
+            DeveloperId::Name(name) => Ok(Self {
+                name: name.replace('+', " "),
+                description: None,
+                header_image: None,
+                icon: None,
+                website: None,
+                featured_app_id: None,
+                id,
+            }),
+        }
+    }
+}
+
+/// A developer's profile and apps.
+#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+pub struct Portfolio {
+    pub profile: Profile,
+    pub apps: Vec<AppMetadata>,
+    /// Full details for each listed app, if requested (apps that could not be found are skipped).
+    pub details: Option<Vec<crate::model::app::App>>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
 pub struct PageExchange {
     pub request: PageRequest,
     pub response: PageResponse,
 }
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
 pub struct PageRequest {
     pub developer_id: DeveloperId,
+    #[serde(with = "app_store_access::language::language_code")]
     pub language: app_store_access::language::Language,
+    #[serde(with = "app_store_access::country::country_code")]
     pub country: app_store_access::country::Country,
     pub number: Option<usize>,
     pub token: Option<String>,
 }
 
-#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, serde::Serialize)]
 pub struct PageResponse {
     pub apps: Vec<AppMetadata>,
     pub token: Option<String>,
@@ -42,7 +103,7 @@ pub struct PageResponse {
 pub mod pagination {
     use crate::model::app::AppMetadata;
 
-    #[derive(Clone, Debug, Default, Eq, PartialEq)]
+    #[derive(Clone, Debug, Default, Eq, PartialEq, serde::Serialize)]
     pub struct InitialNumericIdResponse(super::PageResponse);
 
     impl From<InitialNumericIdResponse> for super::PageResponse {
@@ -88,7 +149,7 @@ pub mod pagination {
         }
     }
 
-    #[derive(Clone, Debug, Default, Eq, PartialEq)]
+    #[derive(Clone, Debug, Default, Eq, PartialEq, serde::Serialize)]
     pub struct InitialNameIdResponse(super::PageResponse);
 
     impl From<InitialNameIdResponse> for super::PageResponse {
@@ -133,7 +194,7 @@ pub mod pagination {
         }
     }
 
-    #[derive(Clone, Debug, Default, Eq, PartialEq)]
+    #[derive(Clone, Debug, Default, Eq, PartialEq, serde::Serialize)]
     pub struct PaginatedNumericIdResponse(super::PageResponse);
 
     impl From<PaginatedNumericIdResponse> for super::PageResponse {
@@ -172,7 +233,7 @@ pub mod pagination {
         }
     }
 
-    #[derive(Clone, Debug, Default, Eq, PartialEq)]
+    #[derive(Clone, Debug, Default, Eq, PartialEq, serde::Serialize)]
     pub struct PaginatedNameIdResponse(super::PageResponse);
 
     impl From<PaginatedNameIdResponse> for super::PageResponse {
@@ -340,6 +401,46 @@ mod internal {
         pub developer_id: String,
     }
 
+    #[derive(serde_query::Deserialize)]
+    pub struct ProfilePage {
+        #[query(".data.[0].[1].[0].[20]")]
+        pub header: ProfileHeader,
+        #[query(".data.[0].[1].[0].[19]")]
+        pub featured_app: Option<ProfileFeaturedApp>,
+    }
+
+    #[derive(serde_query::Deserialize)]
+    pub struct ProfileHeader {
+        #[query(".[0]")]
+        pub name: String,
+        #[query(".[1].[1]")]
+        pub description: Option<String>,
+        #[query(".[2]")]
+        pub header_image: Option<ProfileImage>,
+        #[query(".[3]")]
+        pub icon: Option<ProfileImage>,
+        #[query(".[4]")]
+        pub website: Option<ProfileLink>,
+    }
+
+    #[derive(serde_query::Deserialize)]
+    pub struct ProfileImage {
+        #[query(".[3].[2]")]
+        pub url: String,
+    }
+
+    #[derive(serde_query::Deserialize)]
+    pub struct ProfileLink {
+        #[query(".[0].[2]")]
+        pub url: String,
+    }
+
+    #[derive(serde_query::Deserialize)]
+    pub struct ProfileFeaturedApp {
+        #[query(".[0].[0].[0]")]
+        pub app_id: String,
+    }
+
     #[derive(Clone, Debug, PartialEq, Eq, serde_query::Deserialize)]
     pub struct PaginatedNumericIdResponse {
         #[query(".[0].[6].[0]")]
@@ -360,3 +461,82 @@ mod internal {
         pub app_id: String,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{DeveloperId, PageResponse, Profile, pagination::InitialNumericIdResponse};
+
+    fn numeric_id_page() -> serde_json::Value {
+        let mut section = vec![serde_json::Value::Null; 22];
+
+        section[19] = serde_json::json!([[["com.example.featured"]]]);
+        section[20] = serde_json::json!([
+            "Example Games",
+            [null, "We make puzzle games."],
+            [
+                null,
+                null,
+                null,
+                [null, null, "https://play-lh.googleusercontent.com/header"]
+            ],
+            [
+                null,
+                null,
+                null,
+                [null, null, "https://play-lh.googleusercontent.com/icon"]
+            ],
+            [[null, null, "https://example.com"]]
+        ]);
+        section[21] = serde_json::json!([
+            [[["com.example.featured"]], [["com.example.other"]]],
+            [
+                null,
+                null,
+                [
+                    null,
+                    null,
+                    null,
+                    null,
+                    [null, null, "/store/apps/dev?id=5700313618786177705"]
+                ]
+            ]
+        ]);
+
+        serde_json::json!({ "data": [[null, [section]]] })
+    }
+
+    #[test]
+    fn deserialize_numeric_id_profile() {
+        let id = DeveloperId::Numeric(5_700_313_618_786_177_705);
+        let profile = Profile::from_page(id.clone(), &numeric_id_page()).unwrap();
+
+        assert_eq!(
+            profile,
+            Profile {
+                id,
+                name: "Example Games".to_string(),
+                description: Some("We make puzzle games.".to_string()),
+                header_image: Some("https://play-lh.googleusercontent.com/header".to_string()),
+                icon: Some("https://play-lh.googleusercontent.com/icon".to_string()),
+                website: Some("https://example.com".to_string()),
+                featured_app_id: Some("com.example.featured".to_string()),
+            }
+        );
+
+        let page: PageResponse =
+            serde_json::from_value::<InitialNumericIdResponse>(numeric_id_page())
+                .unwrap()
+                .into();
+
+        assert_eq!(page.apps.len(), 2);
+        assert_eq!(page.token, None);
+    }
+
+    #[test]
+    fn reject_profile_without_header() {
+        let mut page = numeric_id_page();
+        page["data"][0][1][0][20] = serde_json::Value::Null;
+
+        assert!(Profile::from_page(DeveloperId::Numeric(1), &page).is_err());
+    }
+}
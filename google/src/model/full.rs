@@ -139,27 +139,28 @@ pub(super) struct App {
     pub privacy_policy: String,
     #[query(".data.[1].[2].[79].[0].[0]")]
     pub genre: Genre,
+    /// Only present for ad-supported apps.
+    #[query(".data.[1].[2].[48]")]
+    pub ads_section: Option<AdsSection>,
+    /// Only present for apps that offer in-app purchases.
+    #[query(".data.[1].[2].[19]")]
+    pub in_app_purchases_section: Option<InAppPurchasesSection>,
+    #[query(".data.[1].[2].[18]")]
+    pub availability_section: Option<AvailabilitySection>,
     //#[query(".data.[1].[2].[118].[0].[0]")]
     //#[query(".data.[1].[2].[118]")]
     //categories_section: Option<categories_section::CategoriesSection>,
+    /// Missing for apps that have not been rated yet.
+    #[query(".data.[1].[2].[51]")]
+    pub rating_section: Option<RatingSection>,
     /*
-    #[query(".data.[1].[2].[51].[0].[1]")]
-    pub score: f32,
-    #[query(".data.[1].[2].[51].[0].[0]")]
-    pub score_text: String,
-    #[query(".data.[1].[2].[51].[2].[1]")]
-    pub ratings: u64,
     #[query(".data.[1].[2].[51].[3].[1]")]
     pub reviews: u64,
     #[query(".data.[1].[2].[51].[1]")]
     pub histogram: Histogram,
     */
-    //#[query(".data.[1].[2].[18].[0]")]
-    //pub available: u8,
     //#[query(".data.[1].[2].[99].[0].[5].[2]")]
     //pub privacy_policy: String,
-    /*#[query(".data.[1].[2].[48].[0]")]
-    pub ad_supported: String,*/
 }
 
 impl App {
@@ -222,13 +223,53 @@ impl App {
             .map(std::string::String::as_str)
     }*/
 
+    #[must_use]
+    pub fn contains_ads(&self) -> bool {
+        self.ads_section
+            .as_ref()
+            .is_some_and(|section| !section.label.is_empty())
+    }
+
+    #[must_use]
+    pub fn in_app_purchase_range(&self) -> Option<&str> {
+        self.in_app_purchases_section
+            .as_ref()
+            .map(|section| section.price_range.as_str())
+            .filter(|price_range| !price_range.is_empty())
+    }
+
+    #[must_use]
+    pub fn pre_registration(&self) -> bool {
+        self.availability_section
+            .as_ref()
+            .and_then(|section| section.status)
+            == Some(AvailabilitySection::PRE_REGISTRATION)
+    }
+
+    #[must_use]
+    pub fn early_access(&self) -> bool {
+        self.availability_section
+            .as_ref()
+            .is_some_and(|section| section.early_access_label.is_some())
+    }
+
+    #[must_use]
+    pub fn monetization(&self) -> super::app::Monetization {
+        super::app::Monetization {
+            contains_ads: self.contains_ads(),
+            in_app_purchase_range: self.in_app_purchase_range().map(ToString::to_string),
+            pre_registration: self.pre_registration(),
+            early_access: self.early_access(),
+        }
+    }
+
     #[must_use]
     pub fn price(&self) -> Option<crate::model::Price> {
         self.purchase_section.as_ref().map(|purchase_section| {
             match &purchase_section.price_section {
                 purchase_section::PriceSection::Both((price, _))
                 | purchase_section::PriceSection::PriceOnly((price,)) => {
-                    crate::model::Price::new(price.value, price.currency.clone())
+                    crate::model::Price::new(price.micros, price.currency.clone())
                 }
             }
         })
@@ -330,10 +371,10 @@ mod purchase_section {
         PriceOnly((Price,)),
     }
 
-    #[derive(Clone, Debug, PartialEq, serde_query::Deserialize)]
+    #[derive(Clone, Debug, Eq, PartialEq, serde_query::Deserialize)]
     pub struct Price {
         #[query(".[0]")]
-        pub value: f32,
+        pub micros: u64,
         #[query(".[1]")]
         pub currency: String,
     }
@@ -389,7 +430,7 @@ mod version_section {
     }
 }*/
 
-#[derive(Clone, Debug, PartialEq, Eq, serde_query::Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, serde_query::Deserialize, serde::Serialize)]
 pub struct Histogram {
     #[query(".[1].[1]")]
     pub rating_1: u64,
@@ -403,7 +444,7 @@ pub struct Histogram {
     pub rating_5: u64,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, serde_query::Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, serde_query::Deserialize, serde::Serialize)]
 pub struct DeveloperMetadata {
     #[query(".[1].[4].[2]")]
     pub id: String,
@@ -411,7 +452,7 @@ pub struct DeveloperMetadata {
     pub name: String,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
 #[serde(untagged)]
 pub enum DeveloperData {
     Full(FullDeveloperData),
@@ -419,7 +460,7 @@ pub enum DeveloperData {
     Shorter(ShorterDeveloperData),
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, serde_query::Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, serde_query::Deserialize, serde::Serialize)]
 pub struct FullDeveloperData {
     #[query(".[1].[0]")]
     pub email: String,
@@ -431,7 +472,7 @@ pub struct FullDeveloperData {
     pub legal_data: DeveloperLegalData,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, serde_query::Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, serde_query::Deserialize, serde::Serialize)]
 pub struct ShortDeveloperData {
     #[query(".[1].[0]")]
     pub email: String,
@@ -441,13 +482,13 @@ pub struct ShortDeveloperData {
     pub website_wrapper: Option<WebsiteWrapper>,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, serde_query::Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, serde_query::Deserialize, serde::Serialize)]
 pub struct ShorterDeveloperData {
     #[query(".[1].[0]")]
     pub email: String,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, serde_query::Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, serde_query::Deserialize, serde::Serialize)]
 pub struct DeveloperLegalData {
     #[query(".[0]")]
     pub legal_name: Option<String>,
@@ -457,19 +498,19 @@ pub struct DeveloperLegalData {
     pub legal_address: Option<String>,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, serde_query::Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, serde_query::Deserialize, serde::Serialize)]
 pub struct AddressWrapper {
     #[query(".[0]")]
     pub value: String,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, serde_query::Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, serde_query::Deserialize, serde::Serialize)]
 pub struct WebsiteWrapper {
     #[query(".[5].[2]")]
     pub value: String,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, serde_query::Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, serde_query::Deserialize, serde::Serialize)]
 pub struct Genre {
     #[query(".[0]")]
     pub name: String,
@@ -485,6 +526,74 @@ pub(super) struct ContentRating {
     pub description: Option<(serde::de::IgnoredAny, String)>,
 }
 
+#[derive(Clone, Debug, PartialEq, Eq, serde_query::Deserialize)]
+pub(super) struct AdsSection {
+    /// For example "Contains ads".
+    #[query(".[0]")]
+    pub label: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, serde_query::Deserialize)]
+pub(super) struct InAppPurchasesSection {
+    /// For example "$0.99 - $49.99 per item".
+    #[query(".[0]")]
+    pub price_range: String,
+}
+
+/// Release status flags.
+///
+/// The length of this array varies, so only the positions we understand are read.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(super) struct AvailabilitySection {
+    pub status: Option<u64>,
+    /// Only present for apps in early access.
+    pub early_access_label: Option<String>,
+}
+
+impl AvailabilitySection {
+    pub const PRE_REGISTRATION: u64 = 1;
+}
+
+impl<'de> serde::de::Deserialize<'de> for AvailabilitySection {
+    fn deserialize<D: serde::de::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct AvailabilitySectionVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for AvailabilitySectionVisitor {
+            type Value = AvailabilitySection;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("Google app availability section")
+            }
+
+            fn visit_seq<A: serde::de::SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> Result<Self::Value, A::Error> {
+                let status = seq.next_element::<Option<u64>>()?.flatten();
+                let _ = seq.next_element::<serde::de::IgnoredAny>()?;
+                let early_access_label = seq.next_element::<Option<String>>()?.flatten();
+
+                while seq.next_element::<serde::de::IgnoredAny>()?.is_some() {}
+
+                Ok(AvailabilitySection {
+                    status,
+                    early_access_label,
+                })
+            }
+        }
+
+        deserializer.deserialize_seq(AvailabilitySectionVisitor)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, serde_query::Deserialize)]
+pub(super) struct RatingSection {
+    #[query(".[0].[1]")]
+    pub score: f64,
+    #[query(".[2].[1]")]
+    pub count: u64,
+}
+
 #[derive(Clone, Debug, PartialEq, serde_query::Deserialize)]
 pub(super) struct ReleasedSection {
     #[query(".[0]")]
@@ -492,3 +601,141 @@ pub(super) struct ReleasedSection {
     #[query(".[1].[0]")]
     pub timestamp_s: u64,
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{Value, json};
+
+    /// Set a value in nested arrays, padding them with nulls as needed.
+    fn set(target: &mut Value, path: &[usize], value: Value) {
+        match path.split_first() {
+            Some((index, rest)) => {
+                let array = target.as_array_mut().unwrap();
+
+                if array.len() <= *index {
+                    array.resize(index + 1, Value::Null);
+                }
+
+                if !rest.is_empty() && array[*index].is_null() {
+                    array[*index] = json!([]);
+                }
+
+                set(&mut array[*index], rest, value);
+            }
+            None => *target = value,
+        }
+    }
+
+    /// A minimal detail page `ds:5` object, with `sections` added at the given top-level
+    /// positions.
+    fn detail_page(id: &str, sections: &[(usize, Value)]) -> Value {
+        let mut app = json!([]);
+
+        set(&mut app, &[0, 0], json!("Example"));
+        set(&mut app, &[9], json!(["Everyone", null, null]));
+        set(&mut app, &[13], json!(["1,000+", 1000, 1500]));
+        set(
+            &mut app,
+            &[68],
+            json!([
+                "Example Developer",
+                [
+                    null,
+                    null,
+                    null,
+                    null,
+                    [null, null, "/store/apps/dev?id=123"]
+                ]
+            ]),
+        );
+        set(&mut app, &[69], json!([null, ["dev@example.com"]]));
+        set(&mut app, &[72, 0, 1], json!("Description"));
+        set(&mut app, &[73, 0, 1], json!("Summary"));
+        set(&mut app, &[77, 0], json!(id));
+        set(&mut app, &[78, 0], json!([]));
+        set(
+            &mut app,
+            &[79, 0, 0],
+            json!(["Puzzle", null, "GAME_PUZZLE"]),
+        );
+        set(&mut app, &[95, 0, 3, 2], json!("https://example.com/icon"));
+        set(
+            &mut app,
+            &[96, 0, 3, 2],
+            json!("https://example.com/header"),
+        );
+        set(
+            &mut app,
+            &[99, 0, 5, 2],
+            json!("https://example.com/privacy"),
+        );
+
+        for (position, section) in sections {
+            set(&mut app, &[*position], section.clone());
+        }
+
+        let mut data = json!([]);
+        set(&mut data, &[1, 2], app);
+
+        json!({ "key": "ds:5", "data": data })
+    }
+
+    #[test]
+    fn deserialize_monetization() {
+        let with_ads_and_iap = detail_page(
+            "com.example.ads",
+            &[
+                (48, json!(["Contains ads"])),
+                (19, json!(["$0.99 - $49.99 per item", null])),
+                (51, json!([["4.5", 4.52], null, ["1K", 1234]])),
+            ],
+        );
+        let pre_registration = detail_page("com.example.soon", &[(18, json!([1, null]))]);
+        let early_access = detail_page(
+            "com.example.early",
+            &[(18, json!([2, null, "Early access", null]))],
+        );
+        let plain = detail_page("com.example.plain", &[]);
+
+        let app: crate::model::app::App = serde_json::from_value(with_ads_and_iap).unwrap();
+        assert_eq!(
+            app.monetization,
+            crate::model::app::Monetization {
+                contains_ads: true,
+                in_app_purchase_range: Some("$0.99 - $49.99 per item".to_string()),
+                pre_registration: false,
+                early_access: false,
+            }
+        );
+        assert!(app.monetization.in_app_purchases());
+        assert_eq!(
+            app.rating,
+            Some(crate::model::app::Rating {
+                score: 4.52,
+                count: 1234
+            })
+        );
+
+        let app: crate::model::app::App = serde_json::from_value(pre_registration).unwrap();
+        assert!(app.monetization.pre_registration);
+        assert!(!app.monetization.early_access);
+        assert!(!app.monetization.contains_ads);
+        assert!(!app.monetization.in_app_purchases());
+
+        let app: crate::model::app::App = serde_json::from_value(early_access).unwrap();
+        assert!(!app.monetization.pre_registration);
+        assert!(app.monetization.early_access);
+
+        let app: crate::model::app::App = serde_json::from_value(plain).unwrap();
+        assert_eq!(app.monetization, crate::model::app::Monetization::default());
+        assert_eq!(app.rating, None);
+        assert_eq!(app.title, "Example");
+    }
+
+    #[test]
+    fn reject_untyped_monetization_sections() {
+        let page = detail_page("com.example.ads", &[(48, json!([true]))]);
+
+        assert!(serde_json::from_value::<crate::model::app::App>(page).is_err());
+    }
+}
@@ -1,4 +1,19 @@
+//! Models for Google Play responses.
+//!
+//! Google returns positional arrays, which are parsed into the named-field types here. All public
+//! model types also implement `Serialize`, with the following layout:
+//!
+//! * Structs are objects whose keys are the Rust field names (in `snake_case`).
+//! * Optional fields are always present, with `null` for missing values.
+//! * Timestamps are RFC 3339 strings.
+//! * Developer IDs are numbers for numeric IDs and strings for name IDs.
+//! * Countries and languages are lowercase codes, and enums are lowercase names.
+//!
+//! Note that serialized values cannot be deserialized back into these types (deserialization
+//! always expects Google's positional representation).
+
 pub mod app;
+pub mod category;
 pub mod developer;
 pub mod full;
 pub mod review;
@@ -6,20 +21,32 @@ pub mod search;
 #[cfg(not(feature = "strict"))]
 pub mod strict_fix;
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
 pub struct Price {
-    pub value: f32,
+    /// The amount in millionths of the currency unit.
+    pub micros: u64,
     pub currency: String,
 }
 
 impl Price {
     #[must_use]
-    pub const fn new(value: f32, currency: String) -> Self {
-        Self { value, currency }
+    pub const fn new(micros: u64, currency: String) -> Self {
+        Self { micros, currency }
+    }
+
+    /// The price as an exact amount.
+    ///
+    /// Returns `None` if the currency is not a valid currency code.
+    #[must_use]
+    pub fn money(&self) -> Option<app_store_access::money::Money> {
+        Some(app_store_access::money::Money::new(
+            app_store_access::money::Amount::from_micros(i64::try_from(self.micros).ok()?),
+            self.currency.parse().ok()?,
+        ))
     }
 }
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
 pub struct ContentRating {
     pub name: String,
     pub description: Option<String>,
@@ -32,7 +59,7 @@ impl ContentRating {
     }
 }
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
 pub struct Category {
     pub name: String,
     pub id: Option<String>,
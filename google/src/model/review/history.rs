@@ -0,0 +1,130 @@
+//! Detection of edits to reviews across archived review pages.
+
+use chrono::{DateTime, Utc};
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+/// The parts of a review that can change when it is edited.
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd, serde::Serialize)]
+pub struct Version {
+    pub timestamp: DateTime<Utc>,
+    pub score: u8,
+    pub text: Option<String>,
+}
+
+impl From<&super::Review> for Version {
+    fn from(review: &super::Review) -> Self {
+        Self {
+            timestamp: review.timestamp,
+            score: review.score,
+            text: review.text.clone(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+pub struct Edit {
+    pub review_id: Uuid,
+    pub app_id: String,
+    pub before: Version,
+    pub after: Version,
+}
+
+impl Edit {
+    #[must_use]
+    pub const fn score_changed(&self) -> bool {
+        self.before.score != self.after.score
+    }
+
+    #[must_use]
+    pub fn text_changed(&self) -> bool {
+        self.before.text != self.after.text
+    }
+}
+
+/// Collects every observed version of each review.
+#[derive(Clone, Debug, Default)]
+pub struct History {
+    reviews: BTreeMap<Uuid, (String, Vec<Version>)>,
+}
+
+impl History {
+    pub fn add(&mut self, app_id: &str, review: &super::Review) {
+        self.reviews
+            .entry(review.id)
+            .or_insert_with(|| (app_id.to_string(), vec![]))
+            .1
+            .push(review.into());
+    }
+
+    /// All edits, ordered by review ID and then by time.
+    ///
+    /// Versions are ordered by the review's own timestamp, not by when the page was archived.
+    #[must_use]
+    pub fn edits(&self) -> Vec<Edit> {
+        let mut edits = vec![];
+
+        for (review_id, (app_id, versions)) in &self.reviews {
+            let mut versions = versions.clone();
+            versions.sort();
+            versions.dedup();
+
+            for pair in versions.windows(2) {
+                if pair[0].score != pair[1].score || pair[0].text != pair[1].text {
+                    edits.push(Edit {
+                        review_id: *review_id,
+                        app_id: app_id.clone(),
+                        before: pair[0].clone(),
+                        after: pair[1].clone(),
+                    });
+                }
+            }
+        }
+
+        edits
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{Review, User};
+    use chrono::DateTime;
+    use uuid::Uuid;
+
+    fn review(id: Uuid, timestamp_s: i64, score: u8, text: &str) -> Review {
+        Review {
+            id,
+            user: User {
+                id: None,
+                display_name: "A Google user".to_string(),
+                avatar_url: String::new(),
+            },
+            score,
+            timestamp: DateTime::from_timestamp(timestamp_s, 0).unwrap(),
+            text: Some(text.to_string()),
+            thumbs_up: None,
+            version: None,
+            reply: None,
+            criteria: None,
+            edit_timestamp: None,
+        }
+    }
+
+    #[test]
+    fn detect_edits() {
+        let id = Uuid::nil();
+        let mut history = super::History::default();
+
+        history.add("com.example", &review(id, 100, 1, "Broken"));
+        history.add("com.example", &review(id, 100, 1, "Broken"));
+        history.add("com.example", &review(id, 200, 4, "Fixed now"));
+
+        let edits = history.edits();
+
+        assert_eq!(edits.len(), 1);
+        assert!(edits[0].score_changed());
+        assert!(edits[0].text_changed());
+        assert_eq!(edits[0].before.score, 1);
+        assert_eq!(edits[0].after.score, 4);
+    }
+}
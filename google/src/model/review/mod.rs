@@ -3,8 +3,9 @@ use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
 pub mod criterion;
+pub mod history;
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
 pub struct Page {
     pub reviews: Vec<Review>,
     pub token: Option<String>,
@@ -19,36 +20,38 @@ impl From<PageResponse> for Page {
     }
 }
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
 pub struct PageExchange {
     pub request: PageRequest,
     pub response: PageResponse,
 }
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
 pub struct PageRequest {
     pub app_id: String,
+    #[serde(with = "app_store_access::language::language_code")]
     pub language: app_store_access::language::Language,
+    #[serde(with = "app_store_access::country::country_code")]
     pub country: app_store_access::country::Country,
     pub sort_order: SortOrder,
     pub number: usize,
     pub token: Option<String>,
 }
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
 pub struct PageResponse {
     pub reviews: Vec<Review>,
     pub token: Option<String>,
 }
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
 pub struct User {
     pub id: Option<u128>,
     pub display_name: String,
     pub avatar_url: String,
 }
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
 pub struct Review {
     pub id: Uuid,
     pub user: User,
@@ -59,9 +62,11 @@ pub struct Review {
     pub version: Option<String>,
     pub reply: Option<Reply>,
     pub criteria: Option<Vec<(criterion::CriterionType, u32)>>,
+    /// When the review was last edited (only present for reviews edited since they were posted).
+    pub edit_timestamp: Option<DateTime<Utc>>,
 }
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
 pub struct Reply {
     pub display_name: String,
     pub text: String,
@@ -143,6 +148,8 @@ mod internal {
         pub reply_section: Option<ReplySection>,
         #[query(".[12]")]
         pub criteria_section: Option<CriteriaSection>,
+        #[query(".[11]")]
+        pub edit_section: Option<EditSection>,
     }
 
     impl Review {
@@ -190,6 +197,10 @@ mod internal {
                         .map(|criterion| (criterion.criterion_type(), criterion.rating()))
                         .collect()
                 }),
+                edit_timestamp: self
+                    .edit_section
+                    .map(|edit_section| timestamp_s_to_timestamp(edit_section.timestamp_s))
+                    .transpose()?,
             })
         }
     }
@@ -204,6 +215,13 @@ mod internal {
         pub timestamp_s: u64,
     }
 
+    /// Only present for reviews that have been edited since they were first posted.
+    #[derive(Clone, Debug, Eq, PartialEq, serde_query::Deserialize)]
+    pub struct EditSection {
+        #[query(".[0]")]
+        pub timestamp_s: u64,
+    }
+
     #[derive(Clone, Debug, Eq, PartialEq, serde_query::Deserialize)]
     pub struct CriteriaSection {
         #[query(".[0].[]")]
@@ -249,3 +267,92 @@ mod internal {
             })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::PageResponse;
+    use chrono::DateTime;
+
+    #[test]
+    fn deserialize_review_batch() {
+        let page = serde_json::json!([
+            [
+                [
+                    "6f0b5a0c-3f1a-4c6e-9d2b-8a7e5c4d3b21",
+                    [
+                        "A Google user",
+                        [
+                            null,
+                            2,
+                            null,
+                            [null, null, "https://play-lh.googleusercontent.com/a"]
+                        ]
+                    ],
+                    4,
+                    null,
+                    "Works well after the update.",
+                    [1_700_000_500, 0],
+                    12,
+                    [
+                        "Example Games",
+                        "Thanks for the update!",
+                        [1_700_001_000, 0]
+                    ],
+                    null,
+                    ["110398492039485720394"],
+                    "2.1.0",
+                    [1_700_000_500, 0],
+                    [[["vaf_android_tv", [4]]]]
+                ],
+                [
+                    "0c9e2f41-7d3b-4b8a-a5c6-1e2d3f4a5b6c",
+                    [
+                        "Another user",
+                        [
+                            null,
+                            2,
+                            null,
+                            [null, null, "https://play-lh.googleusercontent.com/b"]
+                        ]
+                    ],
+                    1,
+                    null,
+                    null,
+                    [1_700_000_000, 0],
+                    null,
+                    null,
+                    null,
+                    ["110398492039485720395"],
+                    null,
+                    null,
+                    null
+                ]
+            ],
+            [null, "next-page"]
+        ]);
+
+        let page = serde_json::from_value::<PageResponse>(page).unwrap();
+
+        assert_eq!(page.token.as_deref(), Some("next-page"));
+        assert_eq!(page.reviews.len(), 2);
+
+        let edited = &page.reviews[0];
+
+        assert_eq!(edited.score, 4);
+        assert_eq!(
+            edited.edit_timestamp,
+            DateTime::from_timestamp(1_700_000_500, 0)
+        );
+        assert_eq!(
+            edited.reply.as_ref().map(|reply| reply.timestamp),
+            DateTime::from_timestamp(1_700_001_000, 0)
+        );
+        assert_eq!(edited.criteria.as_ref().map(Vec::len), Some(1));
+
+        let unedited = &page.reviews[1];
+
+        assert_eq!(unedited.edit_timestamp, None);
+        assert_eq!(unedited.text, None);
+        assert_eq!(unedited.user.id, Some(110_398_492_039_485_720_395));
+    }
+}
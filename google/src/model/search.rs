@@ -1,6 +1,6 @@
 use crate::request::params::developer::DeveloperId;
 
-#[derive(Clone, Debug, Default, PartialEq)]
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize)]
 pub struct Page {
     pub apps: Option<Vec<App>>,
     pub token: Option<String>,
@@ -28,7 +28,7 @@ impl From<SearchPaginationResult> for Page {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, serde_query::Deserialize)]
+#[derive(Clone, Debug, PartialEq, serde_query::Deserialize, serde::Serialize)]
 pub struct SearchResult {
     #[query(".data.[0].[1]")]
     //pub inner: serde_json::Value,
@@ -52,7 +52,7 @@ impl SearchResult {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, serde_query::Deserialize)]
+#[derive(Clone, Debug, PartialEq, serde_query::Deserialize, serde::Serialize)]
 pub struct SearchResultInner {
     #[query(".[0].[0].[0].[]")]
     pub apps: Vec<App>,
@@ -70,7 +70,7 @@ impl SearchResultInner {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, serde_query::Deserialize)]
+#[derive(Clone, Debug, PartialEq, serde_query::Deserialize, serde::Serialize)]
 pub struct SearchPaginationResult {
     #[query(".[0].[0].[0].[]")]
     pub apps: Vec<App>,
@@ -95,7 +95,17 @@ pub enum TokenWrapper {
     Other(serde::de::IgnoredAny),
 }
 
-#[derive(Clone, Debug, PartialEq, serde_query::Deserialize)]
+/// Serialized as the token (or `null` if there is no token).
+impl serde::Serialize for TokenWrapper {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Token(((), token)) => serializer.serialize_some(token),
+            Self::Other(_) => serializer.serialize_none(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, serde_query::Deserialize, serde::Serialize)]
 pub struct App {
     #[query(".[12].[0]")]
     pub id: String,
@@ -107,10 +117,8 @@ pub struct App {
     pub developer: Developer,
     #[query(".[1].[1].[0].[3].[2]")]
     pub icon: String,
-    //#[query(".[7].[0].[3].[2].[1].[0]")]
-    //pub price: Option<Price>,
     #[query(".[7]")]
-    pub price: Option<serde_json::Value>,
+    pub price: Option<Price>,
     #[query(".[6]")]
     pub score: Option<Score>,
 }
@@ -120,9 +128,18 @@ impl App {
     pub fn developer_id(&self) -> Option<DeveloperId> {
         DeveloperId::parse_url_path(&self.developer.id)
     }
+
+    /// Results without a price section are free.
+    #[must_use]
+    pub const fn is_free(&self) -> bool {
+        match &self.price {
+            Some(price) => price.is_free(),
+            None => true,
+        }
+    }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, serde_query::Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, serde_query::Deserialize, serde::Serialize)]
 pub struct Developer {
     #[query(".[1].[4].[2]")]
     pub id: String,
@@ -130,18 +147,158 @@ pub struct Developer {
     pub name: String,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, serde_query::Deserialize)]
-pub struct Price {
-    #[query(".[0].[3].[2].[1].[0].[1]")]
+/// The price of a search result.
+///
+/// Paid apps that are on sale also include the original price.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize)]
+pub enum Price {
+    Free,
+    Paid {
+        current: PriceValue,
+        original: Option<PriceValue>,
+    },
+}
+
+impl Price {
+    #[must_use]
+    pub const fn is_free(&self) -> bool {
+        matches!(self, Self::Free)
+    }
+
+    #[must_use]
+    pub const fn is_on_sale(&self) -> bool {
+        matches!(
+            self,
+            Self::Paid {
+                original: Some(_),
+                ..
+            }
+        )
+    }
+
+    #[must_use]
+    pub const fn current(&self) -> Option<&PriceValue> {
+        match self {
+            Self::Free => None,
+            Self::Paid { current, .. } => Some(current),
+        }
+    }
+
+    #[must_use]
+    pub const fn original(&self) -> Option<&PriceValue> {
+        match self {
+            Self::Free => None,
+            Self::Paid { original, .. } => original.as_ref(),
+        }
+    }
+}
+
+impl<'de> serde::de::Deserialize<'de> for Price {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = <serde_json::Value as serde::Deserialize>::deserialize(deserializer)?;
+
+        // The section contains a list of offers, with the current price first.
+        let current = value
+            .pointer("/0/3/2/1/0")
+            .and_then(PriceValue::from_value)
+            .ok_or_else(|| serde::de::Error::custom("missing or invalid current price"))?;
+
+        let original = value
+            .pointer("/0/3/2/1/1")
+            .map(|original| {
+                PriceValue::from_value(original)
+                    .ok_or_else(|| serde::de::Error::custom("invalid original price"))
+            })
+            .transpose()?;
+
+        Ok(if current.micros == 0 {
+            Self::Free
+        } else {
+            Self::Paid {
+                original: original.filter(|original| original.micros > current.micros),
+                current,
+            }
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize)]
+pub struct PriceValue {
+    /// The amount in millionths of the currency unit.
+    pub micros: u64,
     pub currency: String,
-    #[query(".[0].[3].[2].[1].[0].[0]")]
-    pub value: u64,
+    /// The price formatted for display (for example `$4.99`).
+    pub display: Option<String>,
 }
 
-#[derive(Clone, Debug, PartialEq, serde_query::Deserialize)]
+impl PriceValue {
+    /// Parse a `[micros, currency, display, ...]` array.
+    fn from_value(value: &serde_json::Value) -> Option<Self> {
+        Some(Self {
+            micros: value.get(0)?.as_u64()?,
+            currency: value.get(1)?.as_str()?.to_string(),
+            display: value
+                .get(2)
+                .and_then(serde_json::Value::as_str)
+                .map(ToString::to_string),
+        })
+    }
+
+    /// Returns `None` if the currency is not a valid currency code.
+    #[must_use]
+    pub fn money(&self) -> Option<app_store_access::money::Money> {
+        Some(app_store_access::money::Money::new(
+            app_store_access::money::Amount::from_micros(i64::try_from(self.micros).ok()?),
+            self.currency.parse().ok()?,
+        ))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, serde_query::Deserialize, serde::Serialize)]
 pub struct Score {
     #[query(".[0].[2].[1].[1]")]
     pub value: f32,
     #[query(".[0].[2].[1].[0]")]
     pub text: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Price;
+
+    #[test]
+    fn deserialize_price() {
+        let free = serde_json::json!([[null, null, null, [null, null, [null, [[0, "USD", ""]]]]]]);
+        let paid = serde_json::json!([[
+            null,
+            null,
+            null,
+            [
+                null,
+                null,
+                [
+                    null,
+                    [[990_000, "USD", "$0.99"], [4_990_000, "USD", "$4.99"]]
+                ]
+            ]
+        ]]);
+
+        assert_eq!(serde_json::from_value::<Price>(free).unwrap(), Price::Free);
+
+        let missing = serde_json::json!([[null, null, null, [null, null, null]]]);
+        let invalid = serde_json::json!([[null, null, null, [null, null, [null, [["0", "USD"]]]]]]);
+
+        assert!(serde_json::from_value::<Price>(missing).is_err());
+        assert!(serde_json::from_value::<Price>(invalid).is_err());
+
+        let paid = serde_json::from_value::<Price>(paid).unwrap();
+
+        assert!(paid.is_on_sale());
+        assert_eq!(paid.current().unwrap().display.as_deref(), Some("$0.99"));
+        assert_eq!(paid.original().unwrap().micros, 4_990_000);
+        assert_eq!(
+            paid.current().unwrap().money().unwrap().to_string(),
+            "0.99 USD"
+        );
+    }
+}
@@ -0,0 +1,76 @@
+//! Keyword rank runs from Google search results.
+use crate::{archive::Data, model::search::App, request::RequestData};
+use app_store_access::{country::Country, index::Store, rank::Run};
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("I/O error")]
+    Io(#[from] std::io::Error),
+    #[error("Archive error")]
+    Store(PathBuf, scraper_trail::archive::store::Error),
+}
+
+/// Build a run from the full (paginated) list of search results.
+#[must_use]
+pub fn run(timestamp: DateTime<Utc>, country: Country, keyword: &str, apps: &[App]) -> Run {
+    Run {
+        timestamp,
+        store: Store::Google,
+        country,
+        keyword: keyword.to_string(),
+        app_ids: apps.iter().map(|app| app.id.clone()).collect(),
+    }
+}
+
+/// Rebuild runs from all archived search pages.
+///
+/// Pagination pages do not include the query, so they are attached to the initial search page
+/// whose continuation token they were requested with. This requires reading the archive in
+/// chronological order.
+pub fn runs<P: AsRef<Path>>(archive: P) -> Result<Vec<Run>, Error> {
+    let store = scraper_trail::archive::store::Store::new(archive.as_ref());
+    let mut runs = vec![];
+    let mut pending = HashMap::<String, usize>::new();
+
+    for (path, entry) in store.entries::<Data>(false)? {
+        let entry = entry.map_err(|error| Error::Store(path, error))?;
+
+        if let Data::Search(page) = entry.exchange.response.data {
+            let apps = page.apps.unwrap_or_default();
+
+            let index = match &entry.request_params.data {
+                RequestData::Search { query, .. } => {
+                    runs.push(run(
+                        entry.exchange.request.timestamp,
+                        entry.request_params.country,
+                        query,
+                        &apps,
+                    ));
+
+                    Some(runs.len() - 1)
+                }
+                RequestData::Pagination { token, .. } => {
+                    let index = pending.remove::<str>(token);
+
+                    if let Some(index) = index {
+                        runs[index]
+                            .app_ids
+                            .extend(apps.iter().map(|app| app.id.clone()));
+                    }
+
+                    index
+                }
+                _ => None,
+            };
+
+            if let Some((index, token)) = index.zip(page.token) {
+                pending.insert(token, index);
+            }
+        }
+    }
+
+    Ok(runs)
+}
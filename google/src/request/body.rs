@@ -9,6 +9,7 @@ use std::sync::LazyLock;
 pub enum Unknown {
     Generic(Generic),
     Review(Review),
+    Suggest(Suggest),
 }
 
 impl FromStr for Unknown {
@@ -17,6 +18,8 @@ impl FromStr for Unknown {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         if s.contains("qnKhOb") {
             s.parse().ok().map(Self::Generic)
+        } else if s.contains("IJ4APc") {
+            s.parse().ok().map(Self::Suggest)
         } else {
             s.parse().ok().map(Self::Review)
         }
@@ -167,6 +170,57 @@ impl FromStr for Review {
     }
 }
 
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Suggest {
+    pub query: String,
+}
+
+impl Suggest {
+    #[must_use]
+    pub const fn new(query: String) -> Self {
+        Self { query }
+    }
+}
+
+impl Display for Suggest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Build the nested JSON programmatically so that the query is properly escaped.
+        let inner_json = serde_json::json!([[null, [&self.query], [10], [2], 4]]).to_string();
+        let outer_json = serde_json::json!([[["IJ4APc", inner_json]]]).to_string();
+
+        write!(f, "f.req={}", urlencoding::encode(&outer_json))
+    }
+}
+
+impl FromStr for Suggest {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let decoded = urlencoding::decode(s.strip_prefix("f.req=").ok_or_else(|| s.to_string())?)
+            .map_err(|_| s.to_string())?;
+
+        let outer =
+            serde_json::from_str::<serde_json::Value>(&decoded).map_err(|_| s.to_string())?;
+
+        outer
+            .get(0)
+            .and_then(|value| value.get(0))
+            .filter(|value| value.get(0).and_then(serde_json::Value::as_str) == Some("IJ4APc"))
+            .and_then(|value| value.get(1))
+            .and_then(serde_json::Value::as_str)
+            .and_then(|inner| serde_json::from_str::<serde_json::Value>(inner).ok())
+            .and_then(|inner| {
+                inner
+                    .get(0)
+                    .and_then(|value| value.get(1))
+                    .and_then(|value| value.get(0))
+                    .and_then(serde_json::Value::as_str)
+                    .map(|query| Self::new(query.to_string()))
+            })
+            .ok_or_else(|| s.to_string())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::request::params::review::SortOrder;
@@ -197,4 +251,15 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn round_trip_suggest_body() {
+        let body = super::Suggest::new("chess \"puzzles\"".to_string());
+
+        assert_eq!(body.to_string().parse::<super::Suggest>().unwrap(), body);
+        assert_eq!(
+            body.to_string().parse::<super::Unknown>().unwrap(),
+            super::Unknown::Suggest(body)
+        );
+    }
 }
@@ -0,0 +1,30 @@
+//! Countries where Google Play operates.
+use app_store_access::country::Country;
+
+/// Countries where Google Play is not available (because of sanctions or, for China, local
+/// regulation).
+pub const UNAVAILABLE: [Country; 5] = [
+    Country::China,
+    Country::Cuba,
+    Country::Iran,
+    Country::NorthKorea,
+    Country::Syria,
+];
+
+/// Whether Google Play operates in the given country.
+///
+/// Google only accepts ISO 3166-1 codes for the `gl` parameter, so non-ISO codes (including `uk`)
+/// are never considered available.
+#[must_use]
+pub fn is_available(country: Country) -> bool {
+    country.is_iso() && !UNAVAILABLE.contains(&country)
+}
+
+/// All known countries where Google Play operates, ordered by country code.
+#[must_use]
+pub fn countries() -> Vec<Country> {
+    Country::known()
+        .into_iter()
+        .filter(|country| is_available(*country))
+        .collect()
+}
@@ -1,4 +1,6 @@
-use crate::request::params::{developer::DeveloperId, review::SortOrder, search::PriceFilter};
+use crate::request::params::{
+    category::Collection, developer::DeveloperId, review::SortOrder, search::PriceFilter,
+};
 use app_store_access::{country::Country, language::Language};
 use chrono::{DateTime, Utc};
 use scraper_trail::request::params::{Params, ParseError};
@@ -9,6 +11,7 @@ const BASE_URL: &str = "https://play.google.com";
 const CONTENT_TYPE: &str = "application/x-www-form-urlencoded;charset=UTF-8";
 
 pub mod body;
+pub mod markets;
 pub mod params;
 pub mod url;
 
@@ -24,6 +27,10 @@ pub enum RequestData<'a> {
         query: Cow<'a, str>,
         price: Option<PriceFilter>,
     },
+    Category {
+        code: Cow<'a, str>,
+        collection: Option<Collection>,
+    },
     Reviews {
         app_id: Cow<'a, str>,
         sort_order: SortOrder,
@@ -34,6 +41,19 @@ pub enum RequestData<'a> {
         number: usize,
         token: Cow<'a, str>,
     },
+    /// A later page of a category listing.
+    ///
+    /// The category is not part of the pagination request, so it is recorded in the URL fragment
+    /// (which is not sent) to keep archived pages identifiable as category listings.
+    CategoryPagination {
+        code: Cow<'a, str>,
+        collection: Option<Collection>,
+        number: usize,
+        token: Cow<'a, str>,
+    },
+    Suggest {
+        query: Cow<'a, str>,
+    },
 }
 
 impl bounded_static::IntoBoundedStatic for RequestData<'_> {
@@ -49,6 +69,10 @@ impl bounded_static::IntoBoundedStatic for RequestData<'_> {
                 query: query.into_static(),
                 price,
             },
+            Self::Category { code, collection } => Self::Static::Category {
+                code: code.into_static(),
+                collection,
+            },
             Self::Reviews {
                 app_id,
                 sort_order,
@@ -64,6 +88,20 @@ impl bounded_static::IntoBoundedStatic for RequestData<'_> {
                 number,
                 token: token.into_static(),
             },
+            Self::CategoryPagination {
+                code,
+                collection,
+                number,
+                token,
+            } => Self::Static::CategoryPagination {
+                code: code.into_static(),
+                collection,
+                number,
+                token: token.into_static(),
+            },
+            Self::Suggest { query } => Self::Static::Suggest {
+                query: query.into_static(),
+            },
         }
     }
 }
@@ -83,6 +121,10 @@ impl bounded_static::ToBoundedStatic for RequestData<'_> {
                 query: query.to_static(),
                 price: *price,
             },
+            Self::Category { code, collection } => Self::Static::Category {
+                code: code.to_static(),
+                collection: *collection,
+            },
             Self::Reviews {
                 app_id,
                 sort_order,
@@ -98,6 +140,20 @@ impl bounded_static::ToBoundedStatic for RequestData<'_> {
                 number: *number,
                 token: token.to_static(),
             },
+            Self::CategoryPagination {
+                code,
+                collection,
+                number,
+                token,
+            } => Self::Static::CategoryPagination {
+                code: code.to_static(),
+                collection: *collection,
+                number: *number,
+                token: token.to_static(),
+            },
+            Self::Suggest { query } => Self::Static::Suggest {
+                query: query.to_static(),
+            },
         }
     }
 }
@@ -149,6 +205,22 @@ impl<'a> Request<'a> {
         }
     }
 
+    pub fn category<S: Into<Cow<'a, str>>>(
+        code: S,
+        collection: Option<Collection>,
+        language: Language,
+        country: Country,
+    ) -> Self {
+        Self {
+            data: RequestData::Category {
+                code: code.into(),
+                collection,
+            },
+            language,
+            country,
+        }
+    }
+
     pub fn reviews<S: Into<Cow<'a, str>>>(
         app_id: S,
         sort_order: SortOrder,
@@ -185,6 +257,36 @@ impl<'a> Request<'a> {
         }
     }
 
+    pub fn category_pagination<S: Into<Cow<'a, str>>>(
+        code: S,
+        collection: Option<Collection>,
+        number: usize,
+        token: S,
+        language: Language,
+        country: Country,
+    ) -> Self {
+        Self {
+            data: RequestData::CategoryPagination {
+                code: code.into(),
+                collection,
+                number,
+                token: token.into(),
+            },
+            language,
+            country,
+        }
+    }
+
+    pub fn suggest<S: Into<Cow<'a, str>>>(query: S, language: Language, country: Country) -> Self {
+        Self {
+            data: RequestData::Suggest {
+                query: query.into(),
+            },
+            language,
+            country,
+        }
+    }
+
     fn url(&self) -> String {
         match &self.data {
             RequestData::Details { app_id } => {
@@ -211,9 +313,33 @@ impl<'a> Request<'a> {
                         .unwrap_or_default()
                 )
             }
+            RequestData::Category { code, collection } => {
+                format!(
+                    "{BASE_URL}/store/apps/category/{code}{}?hl={}&gl={}",
+                    collection
+                        .map(|collection| format!("/collection/{collection}"))
+                        .unwrap_or_default(),
+                    self.language,
+                    self.country,
+                )
+            }
             RequestData::Reviews { .. } | RequestData::Pagination { .. } => {
                 url::Pagination::new(self.language, self.country).to_string()
             }
+            RequestData::CategoryPagination {
+                code, collection, ..
+            } => {
+                format!(
+                    "{}#category/{code}{}",
+                    url::Pagination::new(self.language, self.country),
+                    collection
+                        .map(|collection| format!("/collection/{collection}"))
+                        .unwrap_or_default(),
+                )
+            }
+            RequestData::Suggest { .. } => {
+                url::Suggest::new(self.language, self.country).to_string()
+            }
         }
     }
 
@@ -221,7 +347,8 @@ impl<'a> Request<'a> {
         match &self.data {
             RequestData::Details { .. }
             | RequestData::Developer { .. }
-            | RequestData::Search { .. } => None,
+            | RequestData::Search { .. }
+            | RequestData::Category { .. } => None,
             RequestData::Reviews {
                 app_id,
                 sort_order,
@@ -236,9 +363,27 @@ impl<'a> Request<'a> {
                 )
                 .to_string(),
             ),
-            RequestData::Pagination { number, token } => {
+            RequestData::Pagination { number, token }
+            | RequestData::CategoryPagination { number, token, .. } => {
                 Some(crate::request::body::Generic::new(*number, token.to_string()).to_string())
             }
+            RequestData::Suggest { query } => {
+                Some(crate::request::body::Suggest::new(query.to_string()).to_string())
+            }
+        }
+    }
+
+    /// Parse a category code and optional collection from `{code}[/collection/{collection}]`.
+    fn parse_category(value: &str) -> Result<(Cow<'static, str>, Option<Collection>), ParseError> {
+        let parts = value.split('/').collect::<Vec<_>>();
+
+        match parts.as_slice() {
+            [code] if !code.is_empty() => Ok(((*code).to_string().into(), None)),
+            [code, "collection", collection] if !code.is_empty() => Ok((
+                (*code).to_string().into(),
+                Some(collection.parse().map_err(|_| Self::url_error())?),
+            )),
+            _ => Err(Self::url_error()),
         }
     }
 
@@ -319,6 +464,12 @@ impl<'a> Params for Request<'a> {
                         })
                         .ok_or(Self::url_error())?,
                 },
+                path if path.starts_with("/store/apps/category/") => {
+                    let (code, collection) =
+                        Self::parse_category(&path["/store/apps/category/".len()..])?;
+
+                    RequestData::Category { code, collection }
+                }
                 "/_/PlayStoreUi/data/batchexecute" => {
                     let body = request
                         .body
@@ -333,9 +484,29 @@ impl<'a> Params for Request<'a> {
                             number: review_body.number,
                             token: review_body.token.map(std::convert::Into::into),
                         },
-                        body::Unknown::Generic(generic_body) => RequestData::Pagination {
-                            number: generic_body.number,
-                            token: generic_body.token.into(),
+                        body::Unknown::Generic(generic_body) => {
+                            match url
+                                .fragment()
+                                .and_then(|fragment| fragment.strip_prefix("category/"))
+                            {
+                                Some(category) => {
+                                    let (code, collection) = Self::parse_category(category)?;
+
+                                    RequestData::CategoryPagination {
+                                        code,
+                                        collection,
+                                        number: generic_body.number,
+                                        token: generic_body.token.into(),
+                                    }
+                                }
+                                None => RequestData::Pagination {
+                                    number: generic_body.number,
+                                    token: generic_body.token.into(),
+                                },
+                            }
+                        }
+                        body::Unknown::Suggest(suggest_body) => RequestData::Suggest {
+                            query: suggest_body.query.into(),
                         },
                     }
                 }
@@ -50,6 +50,16 @@ pub mod developer {
         }
     }
 
+    /// Numeric IDs are serialized as JSON numbers and name IDs as strings.
+    impl serde::Serialize for DeveloperId {
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            match self {
+                Self::Numeric(id) => serializer.serialize_u64(*id),
+                Self::Name(name) => serializer.serialize_str(name),
+            }
+        }
+    }
+
     impl DeveloperId {
         #[must_use]
         pub const fn endpoint_path_part(&self) -> &'static str {
@@ -66,8 +76,55 @@ pub mod developer {
     }
 }
 
+pub mod category {
+    /// A ranked collection within a category.
+    #[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+    pub enum Collection {
+        TopFree,
+        TopPaid,
+        TopGrossing,
+    }
+
+    impl Collection {
+        #[must_use]
+        pub const fn code(&self) -> &'static str {
+            match self {
+                Self::TopFree => "topselling_free",
+                Self::TopPaid => "topselling_paid",
+                Self::TopGrossing => "topgrossing",
+            }
+        }
+    }
+
+    impl std::str::FromStr for Collection {
+        type Err = String;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            match s {
+                "topselling_free" | "top-free" => Ok(Self::TopFree),
+                "topselling_paid" | "top-paid" => Ok(Self::TopPaid),
+                "topgrossing" | "top-grossing" => Ok(Self::TopGrossing),
+                other => Err(format!("Unknown collection: {other}")),
+            }
+        }
+    }
+
+    impl std::fmt::Display for Collection {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str(self.code())
+        }
+    }
+
+    impl serde::Serialize for Collection {
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_str(self.code())
+        }
+    }
+}
+
 pub mod review {
-    #[derive(Clone, Copy, Debug, Default, Eq, Ord, PartialEq, PartialOrd)]
+    #[derive(Clone, Copy, Debug, Default, Eq, Ord, PartialEq, PartialOrd, serde::Serialize)]
+    #[serde(rename_all = "lowercase")]
     pub enum SortOrder {
         #[default]
         Newest,
@@ -107,7 +164,8 @@ pub mod review {
 }
 
 pub mod search {
-    #[derive(Clone, Copy, Debug, Default, Eq, Ord, PartialEq, PartialOrd)]
+    #[derive(Clone, Copy, Debug, Default, Eq, Ord, PartialEq, PartialOrd, serde::Serialize)]
+    #[serde(rename_all = "lowercase")]
     pub enum PriceFilter {
         #[default]
         All,
@@ -124,6 +182,16 @@ pub mod search {
                 Self::Paid => 2,
             }
         }
+
+        /// Check whether a search result satisfies the filter.
+        #[must_use]
+        pub const fn matches(&self, app: &crate::model::search::App) -> bool {
+            match self {
+                Self::All => true,
+                Self::Free => app.is_free(),
+                Self::Paid => !app.is_free(),
+            }
+        }
     }
 
     impl std::str::FromStr for PriceFilter {
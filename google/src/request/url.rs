@@ -125,3 +125,26 @@ impl FromStr for Pagination {
             .ok_or_else(|| s.to_string())
     }
 }
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Suggest {
+    pub language: Language,
+    pub country: Country,
+}
+
+impl Suggest {
+    #[must_use]
+    pub const fn new(language: Language, country: Country) -> Self {
+        Self { language, country }
+    }
+}
+
+impl Display for Suggest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{BASE_URL}/_/PlayStoreUi/data/batchexecute?rpcids=IJ4APc&f.sid=-697906427155521722&bl=boq_playuiserver_20190903.08_p0&authuser&soc-app=121&soc-platform=1&soc-device=1&_reqid=1065213&hl={}&gl={}",
+            self.language, self.country
+        )
+    }
+}